@@ -0,0 +1,43 @@
+use std::f64::consts::TAU;
+use std::io;
+use std::ops::ControlFlow;
+use std::time::Duration;
+
+use braillefb::animation::Animation;
+use braillefb::draw::LineStyle;
+use braillefb::shape::Paint;
+
+const WIDTH: usize = 96;
+const HEIGHT: usize = 64;
+
+// A spinning line and a bouncing ball, until Ctrl-C is pressed or 10 seconds have passed
+fn main() -> io::Result<()> {
+    Animation::new(WIDTH, HEIGHT).fps(30).run(|frame| {
+        let t = frame.elapsed().as_secs_f64();
+        let center = (WIDTH as i32 / 2, HEIGHT as i32 / 2);
+        let angle = t * TAU / 4.0;
+        let end = (
+            center.0 + (angle.cos() * 30.0) as i32,
+            center.1 + (angle.sin() * 30.0) as i32,
+        );
+
+        let canvas = frame.canvas();
+        canvas.rect(
+            (0, 0),
+            (WIDTH as u32, HEIGHT as u32),
+            Paint::Stroke(LineStyle::default()),
+        );
+        canvas.line(center, end, LineStyle::solid().width(2));
+
+        // Bounce between the top and the bottom of the canvas
+        let phase = (t * 1.5).fract();
+        let y = HEIGHT as f64 - 6.0 - (phase * (1.0 - phase) * 4.0) * (HEIGHT as f64 - 12.0);
+        canvas.circle((12, y as i32), 4, Paint::Fill);
+
+        if frame.elapsed() < Duration::from_secs(10) {
+            ControlFlow::Continue(())
+        } else {
+            ControlFlow::Break(())
+        }
+    })
+}
@@ -1,7 +1,7 @@
-fn main() {
+use std::io;
+
+fn main() -> io::Result<()> {
     let framebuffer = vec![true; 128 * 64];
     let f = braillefb::Framebuffer::new(&framebuffer, 128, 64);
-    for c in &f {
-        print!("{}", c);
-    }
+    f.write_to(&mut io::stdout().lock())
 }
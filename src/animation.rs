@@ -0,0 +1,295 @@
+//! Running animations in a terminal.
+//!
+//! [`Animation`] calls a closure to draw every frame into a back buffer, swaps it with the front
+//! buffer and writes the front buffer to the terminal with a [`DiffRenderer`], sleeping between
+//! frames to keep to the target frame rate. The terminal is switched to the alternate screen while
+//! the animation runs and is restored when it ends, including when it is stopped with Ctrl-C.
+//!
+//! # Example
+//!
+//! ```no_run
+//! # use std::ops::ControlFlow;
+//! # use braillefb::animation::Animation;
+//! # fn main() -> std::io::Result<()> {
+//! Animation::new(64, 32).fps(30).run(|frame| {
+//!     let x = frame.number() as usize % 64;
+//!     for y in 0..32 {
+//!         frame.canvas().set(x, y);
+//!     }
+//!
+//!     if frame.number() < 300 {
+//!         ControlFlow::Continue(())
+//!     } else {
+//!         ControlFlow::Break(())
+//!     }
+//! })
+//! # }
+//! ```
+
+use std::io::{self, Write};
+use std::ops::ControlFlow;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::OnceLock;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::terminal::{DiffRenderer, Session};
+use crate::Canvas;
+
+// Set by the Ctrl-C handler while an animation is running
+static RUNNING: AtomicBool = AtomicBool::new(false);
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+
+/// A double-buffered animation loop.
+#[derive(Debug, Clone)]
+pub struct Animation {
+    front: Canvas,
+    back: Canvas,
+    frame_duration: Option<Duration>,
+    clear: bool,
+    renderer: DiffRenderer,
+}
+
+/// The state of the frame that is being drawn, passed to the closure given to [`Animation::run`].
+#[derive(Debug)]
+pub struct Frame<'a> {
+    canvas: &'a mut Canvas,
+    previous: &'a Canvas,
+    number: u64,
+    elapsed: Duration,
+    delta: Duration,
+}
+
+impl Animation {
+    /// Create an animation of `width` by `height` pixels, running at 30 frames per second.
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            front: Canvas::new(width, height),
+            back: Canvas::new(width, height),
+            frame_duration: Some(Duration::from_secs(1) / 30),
+            clear: true,
+            renderer: DiffRenderer::new(),
+        }
+    }
+
+    /// Set the target number of frames per second. With 0 frames are drawn as fast as possible.
+    ///
+    /// When a frame takes longer than its share of a second the following frames aren't sped up
+    /// to catch up.
+    pub fn fps(mut self, fps: u32) -> Self {
+        self.frame_duration = (fps > 0).then(|| Duration::from_secs(1) / fps);
+        self
+    }
+
+    /// Set whether the canvas is cleared before each frame is drawn. Defaults to `true`.
+    ///
+    /// Without clearing, the canvas contains the frame before the previous one, as the buffers are
+    /// swapped after every frame. [`Frame::previous`] has the previous frame.
+    pub fn clear(mut self, clear: bool) -> Self {
+        self.clear = clear;
+        self
+    }
+
+    /// Set the renderer used to write frames, for example to change its
+    /// [`full_redraw_ratio`](DiffRenderer::full_redraw_ratio).
+    pub fn renderer(mut self, renderer: DiffRenderer) -> Self {
+        self.renderer = renderer;
+        self
+    }
+
+    /// The last frame that was drawn.
+    pub fn front(&self) -> &Canvas {
+        &self.front
+    }
+
+    /// Run the animation on stdout until `draw` returns [`ControlFlow::Break`] or Ctrl-C is
+    /// pressed.
+    ///
+    /// A Ctrl-C handler is installed the first time an animation is run, pressing Ctrl-C while no
+    /// animation is running exits the process like it would without the handler. If the program
+    /// already has its own handler, it's kept and Ctrl-C doesn't stop the animation.
+    pub fn run<F>(&mut self, draw: F) -> io::Result<()>
+    where
+        F: FnMut(&mut Frame<'_>) -> ControlFlow<()>,
+    {
+        install_handler();
+        self.run_on(io::stdout().lock(), draw)
+    }
+
+    /// Run the animation on `out` until `draw` returns [`ControlFlow::Break`] (or Ctrl-C is
+    /// pressed after [`Animation::run`] installed its handler).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::ops::ControlFlow;
+    /// # use braillefb::animation::Animation;
+    /// let mut animation = Animation::new(4, 4).fps(0);
+    /// let mut out = Vec::new();
+    /// animation
+    ///     .run_on(&mut out, |frame| {
+    ///         let n = frame.number() as usize;
+    ///         frame.canvas().set(n, 0);
+    ///         if n < 3 {
+    ///             ControlFlow::Continue(())
+    ///         } else {
+    ///             ControlFlow::Break(())
+    ///         }
+    ///     })
+    ///     .unwrap();
+    ///
+    /// assert_eq!("⠀⠈\n", &animation.front().to_string());
+    /// ```
+    pub fn run_on<W, F>(&mut self, out: W, mut draw: F) -> io::Result<()>
+    where
+        W: Write,
+        F: FnMut(&mut Frame<'_>) -> ControlFlow<()>,
+    {
+        let mut session = Session::new(out)?;
+        self.renderer.invalidate();
+
+        INTERRUPTED.store(false, Ordering::SeqCst);
+        RUNNING.store(true, Ordering::SeqCst);
+        let result = self.frames(&mut session, &mut draw);
+        RUNNING.store(false, Ordering::SeqCst);
+        result
+    }
+
+    fn frames<W, F>(&mut self, out: &mut W, draw: &mut F) -> io::Result<()>
+    where
+        W: Write,
+        F: FnMut(&mut Frame<'_>) -> ControlFlow<()>,
+    {
+        let start = Instant::now();
+        let mut last = start;
+        let mut deadline = start;
+
+        for number in 0.. {
+            if INTERRUPTED.load(Ordering::SeqCst) {
+                break;
+            }
+
+            let now = if number == 0 { start } else { Instant::now() };
+            if self.clear {
+                self.back.clear();
+            }
+            let mut frame = Frame {
+                canvas: &mut self.back,
+                previous: &self.front,
+                number,
+                elapsed: now - start,
+                delta: now - last,
+            };
+            let flow = draw(&mut frame);
+            last = now;
+
+            core::mem::swap(&mut self.front, &mut self.back);
+            self.renderer.render(&self.front.framebuffer(), out)?;
+            if flow.is_break() {
+                break;
+            }
+
+            if let Some(frame_duration) = self.frame_duration {
+                // Don't try to catch up after a slow frame
+                deadline = (deadline + frame_duration).max(Instant::now());
+                thread::sleep(deadline.saturating_duration_since(Instant::now()));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<'a> Frame<'a> {
+    /// The canvas to draw the frame on.
+    pub fn canvas(&mut self) -> &mut Canvas {
+        self.canvas
+    }
+
+    /// The previous frame, blank for the first frame.
+    pub fn previous(&self) -> &'a Canvas {
+        self.previous
+    }
+
+    /// The number of the frame, starting at 0.
+    pub fn number(&self) -> u64 {
+        self.number
+    }
+
+    /// The time since the first frame.
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    /// The time since the previous frame, zero for the first frame.
+    pub fn delta(&self) -> Duration {
+        self.delta
+    }
+}
+
+fn install_handler() {
+    static INSTALLED: OnceLock<()> = OnceLock::new();
+    INSTALLED.get_or_init(|| {
+        // Failing means there already is a handler, which is documented to be kept
+        let _ = ctrlc::try_set_handler(|| {
+            if RUNNING.load(Ordering::SeqCst) {
+                INTERRUPTED.store(true, Ordering::SeqCst);
+            } else {
+                // The exit code of a process killed by SIGINT
+                std::process::exit(130);
+            }
+        });
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ops::ControlFlow;
+    use std::time::{Duration, Instant};
+
+    use super::Animation;
+
+    #[test]
+    fn double_buffering() {
+        let mut animation = Animation::new(4, 4).fps(0).clear(false);
+        let mut out = Vec::new();
+        animation
+            .run_on(&mut out, |frame| {
+                let n = frame.number() as usize;
+                // The back buffer has the frame before the previous one
+                assert_eq!(n >= 2, frame.canvas().get_pixel(n - n.min(2), 0).unwrap());
+                assert_eq!(n >= 1, frame.previous().get_pixel(n.max(1) - 1, 0).unwrap());
+                frame.canvas().set(n, 0);
+                if n < 3 {
+                    ControlFlow::Continue(())
+                } else {
+                    ControlFlow::Break(())
+                }
+            })
+            .unwrap();
+
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.starts_with("\x1b[?1049h\x1b[?25l\x1b[2J\x1b[1;1H⠁⠀"));
+        assert!(out.ends_with("\x1b[2J\x1b[1;1H⠈⠈\x1b[?25h\x1b[?1049l"));
+    }
+
+    #[test]
+    fn pacing() {
+        let start = Instant::now();
+        let mut deltas = Vec::new();
+        Animation::new(2, 4)
+            .fps(100)
+            .run_on(Vec::new(), |frame| {
+                deltas.push(frame.delta());
+                if frame.number() < 5 {
+                    ControlFlow::Continue(())
+                } else {
+                    ControlFlow::Break(())
+                }
+            })
+            .unwrap();
+
+        assert_eq!(Duration::ZERO, deltas[0]);
+        assert!(deltas[1..].iter().all(|&d| d >= Duration::from_millis(5)));
+        assert!(start.elapsed() >= Duration::from_millis(50));
+    }
+}
@@ -0,0 +1,305 @@
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::source::{Cells, PixelSource};
+use crate::{Framebuffer, CHAR_HEIGHT, CHAR_WIDTH, DOT_BITS};
+
+/// An owned, mutable pixel buffer that can be rendered as braille `char`s.
+///
+/// A `Canvas` owns its pixels, so there is no need to keep a separate `Vec<bool>` along with the
+/// width and height around. Call [`Canvas::framebuffer`] to get a [`Framebuffer`] view of the
+/// pixels without copying them.
+///
+/// Pixels are bit-packed with one `u8` per braille `char` (see [`Framebuffer::from_cells`]), so
+/// rendering a `char` is a single table lookup.
+///
+/// # Example
+///
+/// ```
+/// # use braillefb::Canvas;
+/// let mut canvas = Canvas::new(4, 4);
+/// canvas.set(0, 0);
+/// canvas.set(3, 3);
+///
+/// assert_eq!(Some(true), canvas.get_pixel(0, 0));
+/// assert_eq!("⠁⢀\n", &canvas.to_string());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Canvas {
+    cells: Vec<u8>,
+    width: usize,
+    height: usize,
+}
+
+impl Canvas {
+    /// Create a blank canvas with all pixels unset.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of cells overflows `usize`.
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            cells: vec![0; cells_count(width, height)],
+            width,
+            height,
+        }
+    }
+
+    // The caller is responsible for leaving the dots outside of `width` and `height` unset
+    pub(crate) fn from_cells(cells: Vec<u8>, width: usize, height: usize) -> Self {
+        debug_assert_eq!(cells.len(), cells_count(width, height));
+        Self {
+            cells,
+            width,
+            height,
+        }
+    }
+
+    /// The width of the canvas in pixels.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// The height of the canvas in pixels.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Set the pixel at `x`, `y`. Pixels outside of the canvas are ignored.
+    pub fn set(&mut self, x: usize, y: usize) {
+        self.set_pixel(x, y, true);
+    }
+
+    /// Unset the pixel at `x`, `y`. Pixels outside of the canvas are ignored.
+    pub fn unset(&mut self, x: usize, y: usize) {
+        self.set_pixel(x, y, false);
+    }
+
+    /// Toggle the pixel at `x`, `y`. Pixels outside of the canvas are ignored.
+    pub fn toggle(&mut self, x: usize, y: usize) {
+        if let Some((index, bit)) = self.index(x, y) {
+            self.cells[index] ^= bit;
+        }
+    }
+
+    /// Set the pixel at `x`, `y` to `value`. Pixels outside of the canvas are ignored.
+    pub fn set_pixel(&mut self, x: usize, y: usize, value: bool) {
+        if let Some((index, bit)) = self.index(x, y) {
+            if value {
+                self.cells[index] |= bit;
+            } else {
+                self.cells[index] &= !bit;
+            }
+        }
+    }
+
+    /// Returns the pixel at `x`, `y`, or `None` if it is outside of the canvas.
+    pub fn get_pixel(&self, x: usize, y: usize) -> Option<bool> {
+        self.index(x, y)
+            .map(|(index, bit)| self.cells[index] & bit != 0)
+    }
+
+    /// Unset every pixel.
+    pub fn clear(&mut self) {
+        self.cells.fill(0);
+    }
+
+    /// Set every pixel.
+    pub fn fill(&mut self) {
+        // Dots in the padding of the last column and row of cells must stay unset
+        for y in 0..self.height {
+            for x in 0..self.width {
+                self.set(x, y);
+            }
+        }
+    }
+
+    /// Resize the canvas, keeping pixels anchored to the top-left corner.
+    ///
+    /// Pixels outside of the new bounds are dropped and new pixels are unset.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use braillefb::Canvas;
+    /// let mut canvas = Canvas::new(2, 2);
+    /// canvas.set(1, 1);
+    /// canvas.resize(3, 1);
+    ///
+    /// assert_eq!(Some(false), canvas.get_pixel(1, 0));
+    /// assert_eq!(None, canvas.get_pixel(1, 1));
+    /// ```
+    pub fn resize(&mut self, width: usize, height: usize) {
+        let mut canvas = Canvas::new(width, height);
+        for y in 0..self.height.min(height) {
+            for x in 0..self.width.min(width) {
+                if self.get_pixel(x, y) == Some(true) {
+                    canvas.set(x, y);
+                }
+            }
+        }
+
+        *self = canvas;
+    }
+
+    /// The bit-packed cells of the canvas, in the layout described by [`Framebuffer::from_cells`].
+    pub fn cells(&self) -> &[u8] {
+        &self.cells
+    }
+
+    /// Returns a copy of the pixels of the canvas in row-major order.
+    pub fn to_pixels(&self) -> Vec<bool> {
+        let mut pixels = Vec::with_capacity(self.width * self.height);
+        for y in 0..self.height {
+            for x in 0..self.width {
+                pixels.push(self.get_pixel(x, y) == Some(true));
+            }
+        }
+        pixels
+    }
+
+    /// Returns a [`Framebuffer`] view of the canvas.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use braillefb::Canvas;
+    /// let mut canvas = Canvas::new(4, 4);
+    /// canvas.fill();
+    ///
+    /// let f = canvas.framebuffer();
+    /// assert_eq!(Some('⣿'), f.get(0));
+    /// assert_eq!('\n', f[2]);
+    /// ```
+    pub fn framebuffer(&self) -> Framebuffer<'_, Cells<'_>> {
+        Framebuffer::from_cells(&self.cells, self.width, self.height)
+    }
+
+    // Returns the index of the cell containing `x`, `y` and the bit for the dot within the cell
+    fn index(&self, x: usize, y: usize) -> Option<(usize, u8)> {
+        if x < self.width && y < self.height {
+            let index = x / CHAR_WIDTH + (y / CHAR_HEIGHT) * self.width.div_ceil(CHAR_WIDTH);
+            Some((index, DOT_BITS[y % CHAR_HEIGHT][x % CHAR_WIDTH]))
+        } else {
+            None
+        }
+    }
+}
+
+fn cells_count(width: usize, height: usize) -> usize {
+    width
+        .div_ceil(CHAR_WIDTH)
+        .checked_mul(height.div_ceil(CHAR_HEIGHT))
+        .expect("width * height overflows usize")
+}
+
+impl PixelSource for Canvas {
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        self.height
+    }
+
+    fn pixel(&self, x: usize, y: usize) -> bool {
+        self.get_pixel(x, y) == Some(true)
+    }
+
+    fn cell(&self, col: usize, row: usize) -> u8 {
+        self.cells[col + row * self.width.div_ceil(CHAR_WIDTH)]
+    }
+}
+
+impl fmt::Display for Canvas {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.framebuffer(), f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Canvas;
+
+    #[test]
+    #[should_panic(expected = "width * height overflows usize")]
+    fn new_overflow() {
+        Canvas::new(usize::MAX, usize::MAX);
+    }
+
+    #[test]
+    fn set_unset_toggle() {
+        let mut canvas = Canvas::new(3, 5);
+        canvas.set(2, 4);
+        assert_eq!(Some(true), canvas.get_pixel(2, 4));
+
+        canvas.unset(2, 4);
+        assert_eq!(Some(false), canvas.get_pixel(2, 4));
+
+        canvas.toggle(0, 0);
+        canvas.toggle(1, 0);
+        canvas.toggle(1, 0);
+        assert_eq!(Some(true), canvas.get_pixel(0, 0));
+        assert_eq!(Some(false), canvas.get_pixel(1, 0));
+
+        // Out of bounds is ignored
+        canvas.set(3, 0);
+        canvas.toggle(0, 5);
+        assert_eq!(None, canvas.get_pixel(3, 0));
+        assert_eq!(None, canvas.get_pixel(0, 5));
+    }
+
+    #[test]
+    fn clear_and_fill() {
+        let mut canvas = Canvas::new(2, 4);
+        canvas.fill();
+        assert_eq!("⣿\n", &canvas.to_string());
+
+        canvas.clear();
+        assert_eq!("⠀\n", &canvas.to_string());
+    }
+
+    #[test]
+    fn resize() {
+        let mut canvas = Canvas::new(2, 2);
+        canvas.fill();
+        canvas.resize(4, 4);
+
+        assert_eq!(4, canvas.width());
+        assert_eq!(4, canvas.height());
+        assert_eq!("⠛⠀\n", &canvas.to_string());
+
+        canvas.resize(1, 1);
+        assert_eq!(vec![true], canvas.to_pixels());
+    }
+
+    #[test]
+    fn packed_cells() {
+        // ⠇⠅
+        // ⠉⠁
+        let mut canvas = Canvas::new(3, 5);
+        for (x, y) in [
+            (0, 0),
+            (2, 0),
+            (0, 1),
+            (0, 2),
+            (2, 2),
+            (0, 4),
+            (1, 4),
+            (2, 4),
+        ] {
+            canvas.set(x, y);
+        }
+
+        assert_eq!(
+            &[0b0000_0111, 0b0000_0101, 0b0000_1001, 0b0000_0001],
+            canvas.cells()
+        );
+        assert_eq!("⠇⠅\n⠉⠁\n", &canvas.to_string());
+
+        // Padding dots are never set
+        canvas.fill();
+        assert_eq!("⣿⡇\n⠉⠁\n", &canvas.to_string());
+    }
+}
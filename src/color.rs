@@ -0,0 +1,351 @@
+//! Rendering braille `char`s with per-`char` ANSI colors.
+//!
+//! Each braille `char` can only have a single foreground color, so [`Colored`] picks the average
+//! color of the set dots in each 2x4 cell. Optionally the average color of the unset dots is
+//! used as the background color.
+//!
+//! # Example
+//!
+//! ```
+//! # use braillefb::{color::{ColorMode, Colored}, source::Pixels};
+//! let pixels = [true, false, true, false, true, false, true, false];
+//! let colors = [[255, 0, 0]; 8];
+//!
+//! let colored = Colored::new(Pixels::new(&pixels, 2, 4), &colors).mode(ColorMode::TrueColor);
+//! assert_eq!("\x1b[38;2;255;0;0m⡇\x1b[0m\n", &colored.to_string());
+//! ```
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::{PixelSource, CHARS, CHAR_HEIGHT, CHAR_WIDTH};
+
+/// The escape sequences used for colors.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ColorMode {
+    /// The 16 standard and bright colors, supported by almost every terminal.
+    Ansi16,
+    /// The xterm 256 color palette.
+    Ansi256,
+    /// 24-bit color.
+    TrueColor,
+}
+
+/// Renders a [`PixelSource`] as braille `char`s colored with SGR escape sequences.
+///
+/// Escape sequences are only written when the color changes between `char`s, and colors are reset
+/// at the end of every line.
+#[derive(Debug, Copy, Clone)]
+pub struct Colored<'a, S> {
+    source: S,
+    colors: &'a [[u8; 3]],
+    mode: ColorMode,
+    background: bool,
+}
+
+impl<'a, S: PixelSource> Colored<'a, S> {
+    /// Create a renderer for `source`, with a `colors` slice of RGB colors for each of the pixels
+    /// in row-major order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of supplied `colors` slice is not equal to the width * height of
+    /// `source`, or if width * height overflows.
+    pub fn new(source: S, colors: &'a [[u8; 3]]) -> Self {
+        let len = source
+            .width()
+            .checked_mul(source.height())
+            .expect("width * height overflows usize");
+        assert_eq!(
+            colors.len(),
+            len,
+            "supplied slice does not match width * height"
+        );
+
+        Self {
+            source,
+            colors,
+            mode: ColorMode::TrueColor,
+            background: false,
+        }
+    }
+
+    /// Set the escape sequences used for colors, defaults to [`ColorMode::TrueColor`].
+    pub fn mode(mut self, mode: ColorMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Also set a background color from the unset dots of each `char`, defaults to `false`.
+    /// `char`s without unset dots have the default background color.
+    pub fn background(mut self, background: bool) -> Self {
+        self.background = background;
+        self
+    }
+
+    // The average color of the set (or unset) dots of a cell, `None` if there are no such dots
+    fn average(&self, col: usize, row: usize, set: bool) -> Option<[u8; 3]> {
+        let (width, height) = (self.source.width(), self.source.height());
+        let mut sum = [0u32; 3];
+        let mut count = 0;
+
+        for y in row * CHAR_HEIGHT..((row + 1) * CHAR_HEIGHT).min(height) {
+            for x in col * CHAR_WIDTH..((col + 1) * CHAR_WIDTH).min(width) {
+                if self.source.pixel(x, y) != set {
+                    continue;
+                }
+                let color = self.colors[x + y * width];
+                for (s, c) in sum.iter_mut().zip(color) {
+                    *s += c as u32;
+                }
+                count += 1;
+            }
+        }
+
+        (count > 0).then(|| sum.map(|s| ((s + count / 2) / count) as u8))
+    }
+}
+
+impl<S: PixelSource> fmt::Display for Colored<'_, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let cols = self.source.width().div_ceil(CHAR_WIDTH);
+        let rows = self.source.height().div_ceil(CHAR_HEIGHT);
+
+        for row in 0..rows {
+            let mut foreground = None;
+            let mut background = None;
+
+            for col in 0..cols {
+                // Colors are only changed when they would be visible
+                let fg = self
+                    .average(col, row, true)
+                    .map(|c| Sgr::new(c, self.mode))
+                    .filter(|sgr| foreground != Some(*sgr));
+                // The background shows between the dots, so full cells reset it to the default
+                let bg = self
+                    .background
+                    .then(|| {
+                        self.average(col, row, false)
+                            .map(|c| Sgr::new(c, self.mode))
+                    })
+                    .filter(|sgr| background != *sgr);
+                let bg_code = bg.map(|sgr| sgr.map_or_else(|| "49".into(), Sgr::background));
+
+                match (fg, bg_code) {
+                    (Some(fg), Some(bg)) => write!(f, "\x1b[{};{}m", fg.foreground(), bg)?,
+                    (Some(fg), None) => write!(f, "\x1b[{}m", fg.foreground())?,
+                    (None, Some(bg)) => write!(f, "\x1b[{}m", bg)?,
+                    (None, None) => {}
+                }
+                foreground = fg.or(foreground);
+                background = bg.unwrap_or(background);
+
+                write!(f, "{}", CHARS[self.source.cell(col, row) as usize])?;
+            }
+
+            if foreground.is_some() || background.is_some() {
+                write!(f, "\x1b[0m")?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the luminance of each of the `colors`, using the Rec. 601 weights.
+///
+/// This is useful for picking which dots to set, for example with [`crate::dither`].
+///
+/// # Example
+///
+/// ```
+/// let luma = braillefb::color::luma(&[[255, 255, 255], [255, 0, 0], [0, 0, 0]]);
+/// assert_eq!(vec![255, 76, 0], luma);
+/// ```
+pub fn luma(colors: &[[u8; 3]]) -> Vec<u8> {
+    colors
+        .iter()
+        .map(|&[r, g, b]| ((r as u32 * 299 + g as u32 * 587 + b as u32 * 114 + 500) / 1000) as u8)
+        .collect()
+}
+
+// A color in a particular `ColorMode`
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Sgr {
+    Ansi16(u8),
+    Ansi256(u8),
+    TrueColor([u8; 3]),
+}
+
+impl Sgr {
+    fn new(color: [u8; 3], mode: ColorMode) -> Self {
+        match mode {
+            ColorMode::Ansi16 => Sgr::Ansi16(nearest(color, ANSI16.iter().copied()) as u8),
+            ColorMode::Ansi256 => Sgr::Ansi256(ansi256(color)),
+            ColorMode::TrueColor => Sgr::TrueColor(color),
+        }
+    }
+
+    fn foreground(self) -> String {
+        match self {
+            Sgr::Ansi16(n) if n < 8 => format!("{}", 30 + n),
+            Sgr::Ansi16(n) => format!("{}", 90 + n - 8),
+            Sgr::Ansi256(n) => format!("38;5;{}", n),
+            Sgr::TrueColor([r, g, b]) => format!("38;2;{};{};{}", r, g, b),
+        }
+    }
+
+    fn background(self) -> String {
+        match self {
+            Sgr::Ansi16(n) if n < 8 => format!("{}", 40 + n),
+            Sgr::Ansi16(n) => format!("{}", 100 + n - 8),
+            Sgr::Ansi256(n) => format!("48;5;{}", n),
+            Sgr::TrueColor([r, g, b]) => format!("48;2;{};{};{}", r, g, b),
+        }
+    }
+}
+
+// The default xterm palette
+const ANSI16: [[u8; 3]; 16] = [
+    [0, 0, 0],
+    [205, 0, 0],
+    [0, 205, 0],
+    [205, 205, 0],
+    [0, 0, 238],
+    [205, 0, 205],
+    [0, 205, 205],
+    [229, 229, 229],
+    [127, 127, 127],
+    [255, 0, 0],
+    [0, 255, 0],
+    [255, 255, 0],
+    [92, 92, 255],
+    [255, 0, 255],
+    [0, 255, 255],
+    [255, 255, 255],
+];
+
+// The levels of each channel in the 6x6x6 color cube (16-231)
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+// Picks the closest of the color cube (16-231) and the grayscale ramp (232-255)
+fn ansi256(color: [u8; 3]) -> u8 {
+    let cube = color.map(|c| nearest([c, c, c], CUBE_LEVELS.iter().map(|&l| [l, l, l])));
+    let cube_color = cube.map(|i| CUBE_LEVELS[i]);
+
+    let gray = nearest(color, (0..24).map(|i| [8 + i * 10; 3]));
+    let gray_color = [8 + gray as u8 * 10; 3];
+
+    if distance(color, gray_color) < distance(color, cube_color) {
+        232 + gray as u8
+    } else {
+        (16 + cube[0] * 36 + cube[1] * 6 + cube[2]) as u8
+    }
+}
+
+// The index of the closest of `palette` to `color`
+fn nearest(color: [u8; 3], palette: impl Iterator<Item = [u8; 3]>) -> usize {
+    palette
+        .enumerate()
+        .min_by_key(|(_, p)| distance(color, *p))
+        .map(|(i, _)| i)
+        .unwrap_or(0)
+}
+
+fn distance(a: [u8; 3], b: [u8; 3]) -> u32 {
+    a.iter()
+        .zip(b)
+        .map(|(&a, b)| (a as i32 - b as i32).pow(2) as u32)
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ansi256, ColorMode, Colored};
+    use crate::source::{FromFn, Pixels};
+
+    const RED: [u8; 3] = [255, 0, 0];
+    const BLUE: [u8; 3] = [0, 0, 255];
+
+    #[test]
+    fn collapses_repeated_escapes() {
+        // ⡇⡇⡇
+        let pixels = [true, false, true, false, true, false].repeat(4);
+        let mut colors = [RED; 24];
+        for y in 0..4 {
+            colors[4 + y * 6] = BLUE;
+        }
+
+        let colored = Colored::new(Pixels::new(&pixels, 6, 4), &colors);
+        assert_eq!(
+            "\x1b[38;2;255;0;0m⡇⡇\x1b[38;2;0;0;255m⡇\x1b[0m\n",
+            &colored.to_string()
+        );
+    }
+
+    #[test]
+    fn background() {
+        let pixels = [true, false].repeat(4);
+        let mut colors = [RED; 8];
+        for y in 0..4 {
+            colors[1 + y * 2] = BLUE;
+        }
+
+        let colored = Colored::new(Pixels::new(&pixels, 2, 4), &colors)
+            .mode(ColorMode::Ansi16)
+            .background(true);
+        assert_eq!("\x1b[91;44m⡇\x1b[0m\n", &colored.to_string());
+
+        let colored = colored.mode(ColorMode::Ansi256);
+        assert_eq!("\x1b[38;5;196;48;5;21m⡇\x1b[0m\n", &colored.to_string());
+
+        // Full chars reset the background instead of keeping the previous one
+        let pixels = [[true, false], [true, true]].concat().repeat(4);
+        let colors = [RED, BLUE, RED, RED].repeat(4);
+        let colored = Colored::new(Pixels::new(&pixels, 4, 4), &colors)
+            .mode(ColorMode::Ansi16)
+            .background(true);
+        assert_eq!("\x1b[91;44m⡇\x1b[49m⣿\x1b[0m\n", &colored.to_string());
+    }
+
+    #[test]
+    fn unset_chars_are_not_colored() {
+        let pixels = [false; 8];
+        let colored = Colored::new(Pixels::new(&pixels, 2, 4), &[RED; 8]);
+        assert_eq!("⠀\n", &colored.to_string());
+    }
+
+    #[test]
+    fn averages_set_dots() {
+        let pixels = [true, true, false, false, false, false, false, false];
+        let colors = [
+            RED,
+            BLUE,
+            [0, 255, 0],
+            [0, 255, 0],
+            [0; 3],
+            [0; 3],
+            [0; 3],
+            [0; 3],
+        ];
+        let colored = Colored::new(Pixels::new(&pixels, 2, 4), &colors);
+        assert_eq!("\x1b[38;2;128;0;128m⠉\x1b[0m\n", &colored.to_string());
+    }
+
+    #[test]
+    #[should_panic(expected = "width * height overflows usize")]
+    fn overflow() {
+        Colored::new(FromFn::new(usize::MAX, 2, |_, _| false), &[]);
+    }
+
+    #[test]
+    fn ansi256_palette() {
+        assert_eq!(16, ansi256([0, 0, 0]));
+        assert_eq!(231, ansi256([255, 255, 255]));
+        assert_eq!(196, ansi256(RED));
+        assert_eq!(244, ansi256([128, 128, 128]));
+    }
+}
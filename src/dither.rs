@@ -0,0 +1,399 @@
+//! Converting 8-bit grayscale (luminance) pixels into dots.
+//!
+//! Thresholding a grayscale image by hand throws away most of its detail. [`Dither`] offers
+//! thresholding as well as ordered and error diffusion dithering, producing a `Vec<bool>` that can
+//! be passed straight to [`Framebuffer::new`](crate::Framebuffer::new).
+//!
+//! Brighter pixels become set dots: a luminance of `255` is always set and `0` is never set.
+//!
+//! # Example
+//!
+//! ```
+//! # use braillefb::{dither::{Dither, Method}, Framebuffer};
+//! // A horizontal gradient
+//! let luma = (0..8 * 8).map(|i| (i % 8 * 32) as u8).collect::<Vec<_>>();
+//!
+//! let pixels = Dither::new(Method::FloydSteinberg)
+//!     .serpentine(true)
+//!     .apply(&luma, 8, 8);
+//!
+//! let f = Framebuffer::new(&pixels, 8, 8);
+//! assert_eq!("⠀⢌⢼⢽\n⠀⠔⡕⣿\n", &f.to_string());
+//! ```
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// A method of converting grayscale pixels into dots.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Method {
+    /// Set pixels with a luminance greater than or equal to the threshold.
+    Threshold(u8),
+    /// Threshold using a threshold picked automatically with [Otsu's method][1].
+    ///
+    /// [1]: https://en.wikipedia.org/wiki/Otsu%27s_method
+    Otsu,
+    /// [Ordered dithering][1] with a Bayer matrix.
+    ///
+    /// [1]: https://en.wikipedia.org/wiki/Ordered_dithering
+    Bayer(BayerSize),
+    /// Floyd–Steinberg error diffusion.
+    FloydSteinberg,
+    /// Atkinson error diffusion, which only diffuses 3/4 of the error for a higher contrast.
+    Atkinson,
+    /// Jarvis, Judice, and Ninke error diffusion.
+    JarvisJudiceNinke,
+    /// Sierra (three row) error diffusion.
+    Sierra,
+}
+
+/// The size of the matrix used for [`Method::Bayer`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum BayerSize {
+    X2,
+    X4,
+    X8,
+}
+
+impl BayerSize {
+    fn n(self) -> usize {
+        match self {
+            BayerSize::X2 => 2,
+            BayerSize::X4 => 4,
+            BayerSize::X8 => 8,
+        }
+    }
+}
+
+/// Converts grayscale pixels into dots with the chosen [`Method`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Dither {
+    method: Method,
+    serpentine: bool,
+}
+
+impl Dither {
+    pub fn new(method: Method) -> Self {
+        Self {
+            method,
+            serpentine: false,
+        }
+    }
+
+    /// Scan alternating rows in opposite directions, which reduces the directional artifacts of
+    /// error diffusion. This has no effect on the other methods.
+    pub fn serpentine(mut self, serpentine: bool) -> Self {
+        self.serpentine = serpentine;
+        self
+    }
+
+    /// Convert a `luma` slice of grayscale pixels in row-major order into dots.
+    ///
+    /// # Panics
+    ///
+    /// Panics if length of supplied `luma` slice is not equal to `width * height`, or if
+    /// `width * height` overflows.
+    pub fn apply(&self, luma: &[u8], width: usize, height: usize) -> Vec<bool> {
+        let len = width
+            .checked_mul(height)
+            .expect("width * height overflows usize");
+        assert_eq!(
+            luma.len(),
+            len,
+            "supplied slice does not match width * height"
+        );
+
+        match self.method {
+            Method::Threshold(threshold) => threshold_pixels(luma, threshold),
+            Method::Otsu => threshold_pixels(luma, otsu_threshold(luma)),
+            Method::Bayer(size) => bayer(luma, width, size),
+            Method::FloydSteinberg => self.diffuse(luma, width, height, &FLOYD_STEINBERG),
+            Method::Atkinson => self.diffuse(luma, width, height, &ATKINSON),
+            Method::JarvisJudiceNinke => self.diffuse(luma, width, height, &JARVIS_JUDICE_NINKE),
+            Method::Sierra => self.diffuse(luma, width, height, &SIERRA),
+        }
+    }
+
+    fn diffuse(&self, luma: &[u8], width: usize, height: usize, kernel: &Kernel) -> Vec<bool> {
+        let mut values = luma.iter().map(|&l| l as i32).collect::<Vec<_>>();
+        let mut pixels = vec![false; luma.len()];
+
+        for y in 0..height {
+            let reverse = self.serpentine && y % 2 == 1;
+            for i in 0..width {
+                let x = if reverse { width - 1 - i } else { i };
+                let index = x + y * width;
+
+                let value = values[index];
+                let set = value >= 128;
+                pixels[index] = set;
+                let error = value - if set { 255 } else { 0 };
+
+                for &(dx, dy, weight) in kernel.weights {
+                    // Mirror the kernel when scanning right to left
+                    let dx = if reverse { -dx } else { dx };
+                    let (Some(xx), Some(yy)) =
+                        (x.checked_add_signed(dx), y.checked_add(dy as usize))
+                    else {
+                        continue;
+                    };
+                    if xx >= width || yy >= height {
+                        continue;
+                    }
+                    values[xx + yy * width] += error * weight / kernel.divisor;
+                }
+            }
+        }
+
+        pixels
+    }
+}
+
+/// Returns the threshold picked by [Otsu's method][1] for the supplied grayscale pixels.
+///
+/// Pixels with a luminance greater than or equal to the returned threshold belong to the brighter
+/// of the two classes.
+///
+/// [1]: https://en.wikipedia.org/wiki/Otsu%27s_method
+///
+/// # Example
+///
+/// ```
+/// let luma = [10, 20, 10, 200, 220, 210];
+/// let threshold = braillefb::dither::otsu_threshold(&luma);
+/// assert!(threshold > 20 && threshold <= 200);
+/// ```
+pub fn otsu_threshold(luma: &[u8]) -> u8 {
+    let mut histogram = [0u64; 256];
+    for &l in luma {
+        histogram[l as usize] += 1;
+    }
+
+    let total = luma.len() as f64;
+    let sum: f64 = histogram
+        .iter()
+        .enumerate()
+        .map(|(i, &count)| i as f64 * count as f64)
+        .sum();
+
+    let mut best = (0.0, 0);
+    let mut background_count = 0.0;
+    let mut background_sum = 0.0;
+    for (i, &count) in histogram.iter().enumerate() {
+        background_count += count as f64;
+        background_sum += i as f64 * count as f64;
+
+        let foreground_count = total - background_count;
+        if background_count == 0.0 || foreground_count == 0.0 {
+            continue;
+        }
+
+        let background_mean = background_sum / background_count;
+        let foreground_mean = (sum - background_sum) / foreground_count;
+        let difference = background_mean - foreground_mean;
+        let variance = background_count * foreground_count * difference * difference;
+        if variance > best.0 {
+            best = (variance, i);
+        }
+    }
+
+    // Everything up to and including `best` is background
+    (best.1 + 1).min(255) as u8
+}
+
+fn threshold_pixels(luma: &[u8], threshold: u8) -> Vec<bool> {
+    luma.iter().map(|&l| l >= threshold).collect()
+}
+
+fn bayer(luma: &[u8], width: usize, size: BayerSize) -> Vec<bool> {
+    let n = size.n();
+    let matrix = bayer_matrix(n);
+    let cells = (n * n) as u32;
+
+    luma.iter()
+        .enumerate()
+        .map(|(i, &l)| {
+            let (x, y) = (i % width, i / width);
+            // Compare against a threshold of (m + 0.5) / cells, scaled to 0..256
+            let m = matrix[x % n + (y % n) * n] as u32;
+            l as u32 * cells >= (2 * m + 1) * 128
+        })
+        .collect()
+}
+
+// https://en.wikipedia.org/wiki/Ordered_dithering#Threshold_map
+fn bayer_matrix(n: usize) -> Vec<u8> {
+    let mut matrix = vec![0u8];
+    let mut size = 1;
+    while size < n {
+        let next_size = size * 2;
+        let mut next = vec![0u8; next_size * next_size];
+        for y in 0..size {
+            for x in 0..size {
+                let m = matrix[x + y * size] * 4;
+                next[x + y * next_size] = m;
+                next[(x + size) + y * next_size] = m + 2;
+                next[x + (y + size) * next_size] = m + 3;
+                next[(x + size) + (y + size) * next_size] = m + 1;
+            }
+        }
+        matrix = next;
+        size = next_size;
+    }
+    matrix
+}
+
+// Error diffusion weights as (dx, dy, weight) from the current pixel
+struct Kernel {
+    weights: &'static [(isize, isize, i32)],
+    divisor: i32,
+}
+
+const FLOYD_STEINBERG: Kernel = Kernel {
+    weights: &[(1, 0, 7), (-1, 1, 3), (0, 1, 5), (1, 1, 1)],
+    divisor: 16,
+};
+
+const ATKINSON: Kernel = Kernel {
+    weights: &[
+        (1, 0, 1),
+        (2, 0, 1),
+        (-1, 1, 1),
+        (0, 1, 1),
+        (1, 1, 1),
+        (0, 2, 1),
+    ],
+    divisor: 8,
+};
+
+const JARVIS_JUDICE_NINKE: Kernel = Kernel {
+    weights: &[
+        (1, 0, 7),
+        (2, 0, 5),
+        (-2, 1, 3),
+        (-1, 1, 5),
+        (0, 1, 7),
+        (1, 1, 5),
+        (2, 1, 3),
+        (-2, 2, 1),
+        (-1, 2, 3),
+        (0, 2, 5),
+        (1, 2, 3),
+        (2, 2, 1),
+    ],
+    divisor: 48,
+};
+
+const SIERRA: Kernel = Kernel {
+    weights: &[
+        (1, 0, 5),
+        (2, 0, 3),
+        (-2, 1, 2),
+        (-1, 1, 4),
+        (0, 1, 5),
+        (1, 1, 4),
+        (2, 1, 2),
+        (-1, 2, 2),
+        (0, 2, 3),
+        (1, 2, 2),
+    ],
+    divisor: 32,
+};
+
+#[cfg(test)]
+mod tests {
+    use super::{bayer_matrix, otsu_threshold, BayerSize, Dither, Method};
+
+    const ALL: [Method; 9] = [
+        Method::Threshold(128),
+        Method::Otsu,
+        Method::Bayer(BayerSize::X2),
+        Method::Bayer(BayerSize::X4),
+        Method::Bayer(BayerSize::X8),
+        Method::FloydSteinberg,
+        Method::Atkinson,
+        Method::JarvisJudiceNinke,
+        Method::Sierra,
+    ];
+
+    #[test]
+    fn black_and_white() {
+        for method in ALL {
+            for serpentine in [false, true] {
+                let dither = Dither::new(method).serpentine(serpentine);
+                assert!(
+                    dither.apply(&[0; 64], 8, 8).iter().all(|p| !p),
+                    "{:?}",
+                    method
+                );
+                assert!(
+                    dither.apply(&[255; 64], 8, 8).iter().all(|p| *p),
+                    "{:?}",
+                    method
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn mid_gray_is_half_set() {
+        for method in [
+            Method::Bayer(BayerSize::X2),
+            Method::Bayer(BayerSize::X4),
+            Method::Bayer(BayerSize::X8),
+            Method::FloydSteinberg,
+            Method::JarvisJudiceNinke,
+            Method::Sierra,
+        ] {
+            let pixels = Dither::new(method).apply(&[128; 16 * 16], 16, 16);
+            let set = pixels.iter().filter(|p| **p).count();
+            assert!((120..=136).contains(&set), "{:?} set {}", method, set);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "width * height overflows usize")]
+    fn overflow() {
+        Dither::new(Method::Otsu).apply(&[], usize::MAX, 2);
+    }
+
+    #[test]
+    fn threshold() {
+        let pixels = Dither::new(Method::Threshold(100)).apply(&[99, 100, 101, 0], 2, 2);
+        assert_eq!(vec![false, true, true, false], pixels);
+    }
+
+    #[test]
+    fn otsu() {
+        assert_eq!(51, otsu_threshold(&[50, 50, 50, 200, 200]));
+
+        let pixels = Dither::new(Method::Otsu).apply(&[50, 60, 190, 200], 2, 2);
+        assert_eq!(vec![false, false, true, true], pixels);
+    }
+
+    #[test]
+    fn bayer_matrices() {
+        assert_eq!(vec![0, 2, 3, 1], bayer_matrix(2));
+        assert_eq!(
+            vec![0, 8, 2, 10, 12, 4, 14, 6, 3, 11, 1, 9, 15, 7, 13, 5],
+            bayer_matrix(4)
+        );
+
+        let mut matrix = bayer_matrix(8);
+        matrix.sort();
+        assert_eq!((0..64).collect::<Vec<_>>(), matrix);
+    }
+
+    #[test]
+    fn serpentine() {
+        let luma = (0..8 * 8).map(|i| (i * 4) as u8).collect::<Vec<_>>();
+        let forward = Dither::new(Method::FloydSteinberg).apply(&luma, 8, 8);
+        let serpentine = Dither::new(Method::FloydSteinberg)
+            .serpentine(true)
+            .apply(&luma, 8, 8);
+
+        // The first row is always scanned left to right
+        assert_eq!(forward[..8], serpentine[..8]);
+        assert_ne!(forward, serpentine);
+    }
+}
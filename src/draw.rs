@@ -0,0 +1,268 @@
+//! Drawing lines on a [`Canvas`].
+//!
+//! Coordinates are signed so that lines can start or end outside of the canvas, pixels outside of
+//! the canvas are clipped.
+//!
+//! # Example
+//!
+//! ```
+//! # use braillefb::{draw::LineStyle, Canvas};
+//! let mut canvas = Canvas::new(8, 4);
+//! canvas.line((0, 0), (7, 3), LineStyle::default());
+//! canvas.line((0, 3), (7, 3), LineStyle::dashed(2, 2));
+//!
+//! assert_eq!("⣉⠒⣤⣀\n", &canvas.to_string());
+//! ```
+
+use crate::Canvas;
+
+/// How a line is stroked.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct LineStyle {
+    width: u32,
+    pattern: Pattern,
+}
+
+/// Which pixels along a line are drawn.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Pattern {
+    /// Every pixel is drawn.
+    Solid,
+    /// Repeatedly draw `on` pixels followed by skipping `off` pixels.
+    Dashed { on: u32, off: u32 },
+}
+
+impl LineStyle {
+    /// A solid line that is 1 pixel wide.
+    pub fn solid() -> Self {
+        Self {
+            width: 1,
+            pattern: Pattern::Solid,
+        }
+    }
+
+    /// A dashed line, drawing `on` pixels then skipping `off` pixels.
+    pub fn dashed(on: u32, off: u32) -> Self {
+        Self {
+            width: 1,
+            pattern: Pattern::Dashed { on, off },
+        }
+    }
+
+    /// A dotted line, drawing every other pixel.
+    pub fn dotted() -> Self {
+        Self::dashed(1, 1)
+    }
+
+    /// Set the width of the stroke in pixels. Wide lines have rounded ends.
+    pub fn width(mut self, width: u32) -> Self {
+        self.width = width;
+        self
+    }
+
+    #[cfg(feature = "std")]
+    pub(crate) fn stroke_width(&self) -> u32 {
+        self.width
+    }
+
+    pub(crate) fn is_on(&self, step: i64) -> bool {
+        match self.pattern {
+            Pattern::Solid => true,
+            Pattern::Dashed { on, off } => step % (on as i64 + off as i64).max(1) < on as i64,
+        }
+    }
+}
+
+impl Default for LineStyle {
+    fn default() -> Self {
+        Self::solid()
+    }
+}
+
+impl Canvas {
+    /// Draw a line from `p0` to `p1` (inclusive).
+    ///
+    /// The pixels along the line are the same as [Bresenham's line algorithm][1], and the dash
+    /// pattern starts at `p0`.
+    ///
+    /// [1]: https://en.wikipedia.org/wiki/Bresenham%27s_line_algorithm
+    pub fn line(&mut self, p0: (i32, i32), p1: (i32, i32), style: LineStyle) {
+        let (p0, p1) = ((p0.0 as i64, p0.1 as i64), (p1.0 as i64, p1.1 as i64));
+        let steps = self.segment(p0, p1, style, 0);
+        if style.is_on(steps) {
+            self.stamp(p1.0, p1.1, style.width);
+        }
+    }
+
+    // Draw the line from `p0` to `p1` without its last pixel, with the dash pattern starting at
+    // step `offset`, and return the number of steps to the last pixel
+    pub(crate) fn segment(
+        &mut self,
+        (x0, y0): (i64, i64),
+        (x1, y1): (i64, i64),
+        style: LineStyle,
+        offset: i64,
+    ) -> i64 {
+        let (dx, dy) = (x1 - x0, y1 - y0);
+        let steps = dx.abs().max(dy.abs());
+        if steps == 0 {
+            return 0;
+        }
+
+        // Only walk the part of the line that can touch the canvas
+        let margin = style.width as i64;
+        let (first, last) = match (
+            clip_steps(x0, dx, steps, self.width() as i64, margin),
+            clip_steps(y0, dy, steps, self.height() as i64, margin),
+        ) {
+            (Some((x_first, x_last)), Some((y_first, y_last))) => {
+                (x_first.max(y_first), x_last.min(y_last).min(steps - 1))
+            }
+            _ => return steps,
+        };
+
+        for step in first..=last {
+            if !style.is_on(offset + step) {
+                continue;
+            }
+            let x = x0 + div_round(step as i128 * dx as i128, steps as i128) as i64;
+            let y = y0 + div_round(step as i128 * dy as i128, steps as i128) as i64;
+            self.stamp(x, y, style.width);
+        }
+        steps
+    }
+
+    pub(crate) fn plot(&mut self, x: i64, y: i64) {
+        if let (Ok(x), Ok(y)) = (usize::try_from(x), usize::try_from(y)) {
+            self.set(x, y);
+        }
+    }
+
+    // Draw a round "brush" of `width` pixels centered on `x`, `y`
+    pub(crate) fn stamp(&mut self, x: i64, y: i64, width: u32) {
+        if width <= 1 {
+            self.plot(x, y);
+            return;
+        }
+
+        let width = width as i64;
+        let (lo, hi) = (-(width - 1) / 2, width / 2);
+        // Only the part of the brush on the canvas
+        let (cols, rows) = (self.width() as i64, self.height() as i64);
+        for dy in lo.max(-y)..=hi.min(rows - 1 - y) {
+            for dx in lo.max(-x)..=hi.min(cols - 1 - x) {
+                // Distances are doubled so that even widths can be centered between pixels
+                let (cx, cy) = ((2 * dx - (lo + hi)) as i128, (2 * dy - (lo + hi)) as i128);
+                if cx * cx + cy * cy <= width as i128 * width as i128 {
+                    self.plot(x + dx, y + dy);
+                }
+            }
+        }
+    }
+}
+
+// Returns the range of steps where `start + step * delta / steps` is within `-margin` and
+// `size + margin`, clamped to `0..=steps`.
+fn clip_steps(start: i64, delta: i64, steps: i64, size: i64, margin: i64) -> Option<(i64, i64)> {
+    let (min, max) = (-margin, size + margin);
+    if delta == 0 {
+        return (min..=max).contains(&start).then_some((0, steps));
+    }
+
+    // Truncating division is off by less than one, the extra step on each side covers rounding
+    let to_step = |c: i64| ((c - start) as i128 * steps as i128 / delta as i128) as i64;
+    let (a, b) = (to_step(min), to_step(max));
+    let first = (a.min(b) - 2).max(0);
+    let last = (a.max(b) + 2).min(steps);
+    (first <= last).then_some((first, last))
+}
+
+// Divide rounding to the nearest integer, `d` must be positive (or `n` zero)
+pub(crate) fn div_round(n: i128, d: i128) -> i128 {
+    if d == 0 {
+        return 0;
+    }
+    (2 * n + d).div_euclid(2 * d)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LineStyle;
+    use crate::Canvas;
+
+    fn pixels(canvas: &Canvas) -> Vec<(usize, usize)> {
+        let mut pixels = Vec::new();
+        for y in 0..canvas.height() {
+            for x in 0..canvas.width() {
+                if canvas.get_pixel(x, y) == Some(true) {
+                    pixels.push((x, y));
+                }
+            }
+        }
+        pixels
+    }
+
+    #[test]
+    fn lines() {
+        let mut canvas = Canvas::new(6, 4);
+        canvas.line((0, 0), (5, 2), LineStyle::default());
+        assert_eq!(
+            vec![(0, 0), (1, 0), (2, 1), (3, 1), (4, 2), (5, 2)],
+            pixels(&canvas)
+        );
+
+        // Steep and reversed
+        let mut canvas = Canvas::new(4, 4);
+        canvas.line((1, 3), (0, 0), LineStyle::default());
+        assert_eq!(vec![(0, 0), (0, 1), (1, 2), (1, 3)], pixels(&canvas));
+
+        // A single point
+        let mut canvas = Canvas::new(4, 4);
+        canvas.line((2, 2), (2, 2), LineStyle::default());
+        assert_eq!(vec![(2, 2)], pixels(&canvas));
+    }
+
+    #[test]
+    fn patterns() {
+        let mut canvas = Canvas::new(8, 1);
+        canvas.line((0, 0), (7, 0), LineStyle::dotted());
+        assert_eq!(vec![(0, 0), (2, 0), (4, 0), (6, 0)], pixels(&canvas));
+
+        let mut canvas = Canvas::new(8, 1);
+        canvas.line((0, 0), (7, 0), LineStyle::dashed(3, 2));
+        assert_eq!(
+            vec![(0, 0), (1, 0), (2, 0), (5, 0), (6, 0), (7, 0)],
+            pixels(&canvas)
+        );
+    }
+
+    #[test]
+    fn width() {
+        let mut canvas = Canvas::new(6, 6);
+        canvas.line((1, 2), (4, 2), LineStyle::solid().width(3));
+        assert_eq!(6 * 3, pixels(&canvas).len());
+        assert_eq!(Some(true), canvas.get_pixel(0, 1));
+        assert_eq!(Some(false), canvas.get_pixel(0, 0));
+
+        // Only the part of a huge brush on the canvas is drawn
+        let mut canvas = Canvas::new(6, 6);
+        canvas.line((1, 2), (4, 2), LineStyle::solid().width(u32::MAX));
+        assert_eq!(6 * 6, pixels(&canvas).len());
+    }
+
+    #[test]
+    fn clipping() {
+        let mut canvas = Canvas::new(4, 4);
+        canvas.line((-2, -2), (5, 5), LineStyle::default());
+        assert_eq!(vec![(0, 0), (1, 1), (2, 2), (3, 3)], pixels(&canvas));
+
+        // Lines far outside of the canvas don't walk every pixel
+        let mut canvas = Canvas::new(4, 4);
+        canvas.line((i32::MIN, 1), (i32::MAX, 1), LineStyle::default());
+        assert_eq!(vec![(0, 1), (1, 1), (2, 1), (3, 1)], pixels(&canvas));
+
+        let mut canvas = Canvas::new(4, 4);
+        canvas.line((-10, -1), (10, -1), LineStyle::default());
+        assert!(pixels(&canvas).is_empty());
+    }
+}
@@ -0,0 +1,129 @@
+use core::convert::Infallible;
+
+use embedded_graphics_core::draw_target::DrawTarget;
+use embedded_graphics_core::geometry::{Dimensions, OriginDimensions, Size};
+use embedded_graphics_core::pixelcolor::BinaryColor;
+use embedded_graphics_core::primitives::Rectangle;
+use embedded_graphics_core::Pixel;
+
+use crate::Canvas;
+
+/// Draw with [`embedded-graphics`][1] primitives, fonts and images.
+///
+/// [`BinaryColor::On`] sets pixels and [`BinaryColor::Off`] unsets them, pixels outside of the
+/// canvas are ignored.
+///
+/// # Example
+///
+/// ```
+/// # use braillefb::Canvas;
+/// use embedded_graphics::pixelcolor::BinaryColor;
+/// use embedded_graphics::prelude::*;
+/// use embedded_graphics::primitives::{Line, PrimitiveStyle, Rectangle};
+///
+/// let mut canvas = Canvas::new(8, 4);
+/// Rectangle::new(Point::new(0, 0), Size::new(4, 4))
+///     .into_styled(PrimitiveStyle::with_stroke(BinaryColor::On, 1))
+///     .draw(&mut canvas)
+///     .unwrap();
+/// Line::new(Point::new(4, 3), Point::new(7, 0))
+///     .into_styled(PrimitiveStyle::with_stroke(BinaryColor::On, 1))
+///     .draw(&mut canvas)
+///     .unwrap();
+///
+/// assert_eq!("⣏⣹⡠⠊\n", &canvas.to_string());
+/// ```
+///
+/// [1]: https://docs.rs/embedded-graphics
+impl DrawTarget for Canvas {
+    type Color = BinaryColor;
+    type Error = Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        for Pixel(point, color) in pixels {
+            if let (Ok(x), Ok(y)) = (usize::try_from(point.x), usize::try_from(point.y)) {
+                self.set_pixel(x, y, color.is_on());
+            }
+        }
+        Ok(())
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        // Only visit the part of the area that is on the canvas
+        let area = area.intersection(&self.bounding_box());
+        for y in area.rows() {
+            for x in area.columns() {
+                self.set_pixel(x as usize, y as usize, color.is_on());
+            }
+        }
+        Ok(())
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        if color.is_on() {
+            self.fill();
+        } else {
+            self.clear();
+        }
+        Ok(())
+    }
+}
+
+impl OriginDimensions for Canvas {
+    fn size(&self) -> Size {
+        let saturate = |n: usize| u32::try_from(n).unwrap_or(u32::MAX);
+        Size::new(saturate(self.width()), saturate(self.height()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_graphics::mono_font::ascii::FONT_4X6;
+    use embedded_graphics::mono_font::MonoTextStyle;
+    use embedded_graphics::pixelcolor::BinaryColor;
+    use embedded_graphics::prelude::*;
+    use embedded_graphics::primitives::{Circle, PrimitiveStyle, Rectangle};
+    use embedded_graphics::text::{Baseline, Text};
+
+    use crate::Canvas;
+
+    #[test]
+    fn size() {
+        assert_eq!(Size::new(5, 7), Canvas::new(5, 7).size());
+    }
+
+    #[test]
+    fn fill_and_clear() {
+        let mut canvas = Canvas::new(4, 4);
+        Rectangle::new(Point::new(-2, 1), Size::new(10, 2))
+            .into_styled(PrimitiveStyle::with_fill(BinaryColor::On))
+            .draw(&mut canvas)
+            .unwrap();
+        assert_eq!("⠶⠶\n", &canvas.to_string());
+
+        DrawTarget::clear(&mut canvas, BinaryColor::On).unwrap();
+        assert_eq!("⣿⣿\n", &canvas.to_string());
+        Circle::new(Point::new(-1, -1), 4)
+            .into_styled(PrimitiveStyle::with_fill(BinaryColor::Off))
+            .draw(&mut canvas)
+            .unwrap();
+        assert_eq!(Some(false), canvas.get_pixel(1, 1));
+        assert_eq!(Some(true), canvas.get_pixel(3, 3));
+
+        DrawTarget::clear(&mut canvas, BinaryColor::Off).unwrap();
+        assert_eq!("⠀⠀\n", &canvas.to_string());
+    }
+
+    #[test]
+    fn text() {
+        let mut canvas = Canvas::new(8, 8);
+        let style = MonoTextStyle::new(&FONT_4X6, BinaryColor::On);
+        Text::with_baseline("Hi", Point::zero(), style, Baseline::Top)
+            .draw(&mut canvas)
+            .unwrap();
+        assert_eq!("⡧⡇⢬⠀\n⠁⠁⠉⠁\n", &canvas.to_string());
+    }
+}
@@ -0,0 +1,32 @@
+use core::fmt;
+
+/// The error type returned when a [`Framebuffer`](crate::Framebuffer) can't be created.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// The length of the supplied slice doesn't match the dimensions.
+    LengthMismatch { expected: usize, actual: usize },
+    /// The dimensions are too large to be represented.
+    DimensionOverflow { width: usize, height: usize },
+    /// The width or height is zero.
+    ZeroSized { width: usize, height: usize },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::LengthMismatch { expected, actual } => write!(
+                f,
+                "supplied slice has a length of {} but {} was expected",
+                actual, expected
+            ),
+            Error::DimensionOverflow { width, height } => {
+                write!(f, "dimensions {}x{} overflow", width, height)
+            }
+            Error::ZeroSized { width, height } => {
+                write!(f, "dimensions {}x{} are zero-sized", width, height)
+            }
+        }
+    }
+}
+
+impl core::error::Error for Error {}
@@ -0,0 +1,584 @@
+//! Drawing text on a [`Canvas`] with bitmap fonts.
+//!
+//! Each pixel of a glyph becomes a single dot, so even small fonts fit a lot of text into a few
+//! braille `char`s. Two fonts covering printable ASCII are built in, [`Font::small`] (3x5 pixels)
+//! and [`Font::standard`] (5x7 pixels). Other fonts can be loaded from BDF files with
+//! [`Font::from_bdf`] and from Linux console PSF files with [`Font::from_psf`].
+//!
+//! # Example
+//!
+//! ```
+//! # use braillefb::{font::Font, Canvas};
+//! let font = Font::small();
+//! let mut canvas = Canvas::new(8, 8);
+//! canvas.draw_text(0, 0, "HI", &font);
+//!
+//! assert_eq!("⡧⡇⢹⠁\n⠁⠁⠉⠁\n", &canvas.to_string());
+//! ```
+
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::Canvas;
+
+mod bdf;
+mod psf;
+
+/// The error type returned when a font can't be loaded.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum FontError {
+    /// The data isn't a PSF1 or PSF2 font.
+    InvalidMagic,
+    /// The header of a PSF2 font is inconsistent.
+    InvalidHeader,
+    /// A BDF font or a PSF Unicode table isn't valid UTF-8.
+    InvalidUtf8,
+    /// A line of a BDF font couldn't be parsed, starting at 1.
+    Syntax { line: usize },
+    /// A required BDF keyword is missing.
+    Missing(&'static str),
+    /// The data ended in the middle of the font.
+    UnexpectedEof,
+}
+
+impl fmt::Display for FontError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FontError::InvalidMagic => write!(f, "not a PSF1 or PSF2 font"),
+            FontError::InvalidHeader => write!(f, "invalid PSF2 header"),
+            FontError::InvalidUtf8 => write!(f, "font is not valid UTF-8"),
+            FontError::Syntax { line } => write!(f, "invalid BDF font at line {}", line),
+            FontError::Missing(keyword) => write!(f, "BDF font is missing {}", keyword),
+            FontError::UnexpectedEof => write!(f, "unexpected end of font data"),
+        }
+    }
+}
+
+impl core::error::Error for FontError {}
+
+/// The pixels of a single `char` of a [`Font`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Glyph {
+    pixels: Vec<bool>,
+    width: usize,
+    height: usize,
+    offset: (i32, i32),
+    advance: usize,
+}
+
+impl Glyph {
+    /// Create a glyph from its `pixels` in row-major order.
+    ///
+    /// The glyph is drawn at the pen position and advances the pen by its `width`, use
+    /// [`Glyph::with_offset`] and [`Glyph::with_advance`] to change that.
+    ///
+    /// # Panics
+    ///
+    /// Panics if length of supplied `pixels` is not equal to `width` * `height`.
+    pub fn new(pixels: Vec<bool>, width: usize, height: usize) -> Self {
+        assert_eq!(
+            pixels.len(),
+            width * height,
+            "supplied pixels do not match width * height"
+        );
+
+        Self {
+            pixels,
+            width,
+            height,
+            offset: (0, 0),
+            advance: width,
+        }
+    }
+
+    /// Draw the glyph `x` pixels to the right and `y` pixels below the pen position.
+    pub fn with_offset(mut self, x: i32, y: i32) -> Self {
+        self.offset = (x, y);
+        self
+    }
+
+    /// Move the pen `advance` pixels to the right after drawing the glyph.
+    pub fn with_advance(mut self, advance: usize) -> Self {
+        self.advance = advance;
+        self
+    }
+
+    /// The width of the glyph in pixels.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// The height of the glyph in pixels.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Where the glyph is drawn relative to the pen position.
+    pub fn offset(&self) -> (i32, i32) {
+        self.offset
+    }
+
+    /// How far the pen moves to the right after drawing the glyph.
+    pub fn advance(&self) -> usize {
+        self.advance
+    }
+
+    /// Returns `true` if the pixel at `x`, `y` is set, `false` if it's unset or outside of the
+    /// glyph.
+    pub fn pixel(&self, x: usize, y: usize) -> bool {
+        x < self.width && y < self.height && self.pixels[x + y * self.width]
+    }
+}
+
+/// A bitmap font, mapping `char`s to [`Glyph`]s.
+///
+/// # Example
+///
+/// ```
+/// # use braillefb::font::{Font, Glyph};
+/// // A font with a single 2x2 glyph
+/// let mut font = Font::new(3);
+/// font.insert('o', Glyph::new(vec![true; 4], 2, 2).with_advance(3));
+///
+/// assert_eq!((5, 5), font.measure("oo\noo"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Font {
+    glyphs: BTreeMap<char, Glyph>,
+    line_height: usize,
+    fallback: Option<char>,
+}
+
+impl Font {
+    /// Create an empty font where each line of text is `line_height` pixels below the previous
+    /// one.
+    pub fn new(line_height: usize) -> Self {
+        Self {
+            glyphs: BTreeMap::new(),
+            line_height,
+            fallback: None,
+        }
+    }
+
+    /// A 3x5 pixel font for printable ASCII. Lowercase letters are drawn as uppercase letters.
+    pub fn small() -> Self {
+        let mut font = Self::new(6);
+        for (c, bits) in (' '..='~').zip(SMALL) {
+            let pixels = (0..15).rev().map(|i| (bits >> i) & 1 == 1).collect();
+            font.insert(c, Glyph::new(pixels, 3, 5).with_advance(4));
+        }
+        font.set_fallback(Some('?'));
+        font
+    }
+
+    /// A 5x7 pixel font for printable ASCII.
+    pub fn standard() -> Self {
+        let mut font = Self::new(8);
+        for (c, columns) in (' '..='~').zip(STANDARD) {
+            let pixels = (0..35)
+                .map(|i| (columns[i % 5] >> (i / 5)) & 1 == 1)
+                .collect();
+            font.insert(c, Glyph::new(pixels, 5, 7).with_advance(6));
+        }
+        font.set_fallback(Some('?'));
+        font
+    }
+
+    /// Add a glyph for `c`, returning the glyph it replaced.
+    pub fn insert(&mut self, c: char, glyph: Glyph) -> Option<Glyph> {
+        self.glyphs.insert(c, glyph)
+    }
+
+    /// Returns the glyph for `c`, or the fallback glyph if the font doesn't have one.
+    pub fn glyph(&self, c: char) -> Option<&Glyph> {
+        self.glyphs
+            .get(&c)
+            .or_else(|| self.fallback.and_then(|f| self.glyphs.get(&f)))
+    }
+
+    /// Draw the glyph of `fallback` in place of `char`s that aren't in the font. Without a
+    /// fallback these `char`s are skipped.
+    pub fn set_fallback(&mut self, fallback: Option<char>) {
+        self.fallback = fallback;
+    }
+
+    /// The distance between lines of text in pixels.
+    pub fn line_height(&self) -> usize {
+        self.line_height
+    }
+
+    /// Iterate over the `char`s in the font and their glyphs, in `char` order.
+    pub fn glyphs(&self) -> impl Iterator<Item = (char, &Glyph)> {
+        self.glyphs.iter().map(|(c, g)| (*c, g))
+    }
+
+    /// Returns the width and height in pixels of the area covered by `text`.
+    ///
+    /// Use this to align text, for example to center a label.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use braillefb::{font::Font, Canvas};
+    /// let font = Font::small();
+    /// let (width, _) = font.measure("OK");
+    /// assert_eq!(7, width);
+    ///
+    /// let mut canvas = Canvas::new(12, 5);
+    /// canvas.draw_text(((canvas.width() - width) / 2) as i32, 0, "OK", &font);
+    /// assert_eq!("⠀⡎⡆⡧⡃⠀\n⠀⠈⠀⠁⠁⠀\n", &canvas.to_string());
+    /// ```
+    pub fn measure(&self, text: &str) -> (usize, usize) {
+        let (mut width, mut height) = (0, 0);
+        for (line, (_, glyphs)) in self.layout(text).enumerate() {
+            let mut bottom = 0;
+            for (x, glyph) in glyphs {
+                width = width.max(x + glyph.offset.0 as i64 + glyph.width as i64);
+                bottom = bottom.max(glyph.offset.1 as i64 + glyph.height as i64);
+            }
+            height = (line * self.line_height) as i64 + bottom;
+        }
+        (width.max(0) as usize, height.max(0) as usize)
+    }
+
+    // The vertical position of each line of `text` and the glyphs on it, with their horizontal
+    // positions
+    fn layout<'a>(
+        &'a self,
+        text: &'a str,
+    ) -> impl Iterator<Item = (i64, impl Iterator<Item = (i64, &'a Glyph)>)> {
+        text.lines().enumerate().map(move |(line, text)| {
+            let mut x = 0;
+            let glyphs = text.chars().filter_map(move |c| {
+                let glyph = self.glyph(c)?;
+                let position = x;
+                x += glyph.advance as i64;
+                Some((position, glyph))
+            });
+            ((line * self.line_height) as i64, glyphs)
+        })
+    }
+}
+
+impl Canvas {
+    /// Draw `text` with its top-left corner at `x`, `y`.
+    ///
+    /// Each line of `text` is drawn [`Font::line_height`] pixels below the previous one, and
+    /// pixels outside of the canvas are clipped.
+    pub fn draw_text(&mut self, x: i32, y: i32, text: &str, font: &Font) {
+        for (line_y, glyphs) in font.layout(text) {
+            for (glyph_x, glyph) in glyphs {
+                let left = x as i64 + glyph_x + glyph.offset.0 as i64;
+                let top = y as i64 + line_y + glyph.offset.1 as i64;
+                for gy in 0..glyph.height {
+                    for gx in 0..glyph.width {
+                        if glyph.pixel(gx, gy) {
+                            self.plot(left + gx as i64, top + gy as i64);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+// 3x5 glyphs for ' ' to '~', one row per 3 bits with the top-left pixel in bit 14
+const SMALL: [u16; 95] = [
+    0x0000, // ' '
+    0x2482, // '!'
+    0x5A00, // '"'
+    0x5F7D, // '#'
+    0x3C9E, // '$'
+    0x42A1, // '%'
+    0x2AAB, // '&'
+    0x2400, // "'"
+    0x1491, // '('
+    0x4494, // ')'
+    0x0AA8, // '*'
+    0x05D0, // '+'
+    0x0014, // ','
+    0x01C0, // '-'
+    0x0002, // '.'
+    0x12A4, // '/'
+    0x7B6F, // '0'
+    0x2C97, // '1'
+    0x73E7, // '2'
+    0x73CF, // '3'
+    0x5BC9, // '4'
+    0x79CF, // '5'
+    0x79EF, // '6'
+    0x7249, // '7'
+    0x7BEF, // '8'
+    0x7BCF, // '9'
+    0x0410, // ':'
+    0x0414, // ';'
+    0x1511, // '<'
+    0x0E38, // '='
+    0x4454, // '>'
+    0x6282, // '?'
+    0x2BE3, // '@'
+    0x2BED, // 'A'
+    0x6BAE, // 'B'
+    0x3923, // 'C'
+    0x6B6E, // 'D'
+    0x79A7, // 'E'
+    0x79A4, // 'F'
+    0x396B, // 'G'
+    0x5BED, // 'H'
+    0x7497, // 'I'
+    0x126A, // 'J'
+    0x5BAD, // 'K'
+    0x4927, // 'L'
+    0x5FED, // 'M'
+    0x6B6D, // 'N'
+    0x2B6A, // 'O'
+    0x6BA4, // 'P'
+    0x2B73, // 'Q'
+    0x6BAD, // 'R'
+    0x388E, // 'S'
+    0x7492, // 'T'
+    0x5B6B, // 'U'
+    0x5B52, // 'V'
+    0x5BFD, // 'W'
+    0x5AAD, // 'X'
+    0x5A92, // 'Y'
+    0x72A7, // 'Z'
+    0x6926, // '['
+    0x4889, // '\\'
+    0x324B, // ']'
+    0x2A00, // '^'
+    0x0007, // '_'
+    0x4400, // '`'
+    0x2BED, // 'a'
+    0x6BAE, // 'b'
+    0x3923, // 'c'
+    0x6B6E, // 'd'
+    0x79A7, // 'e'
+    0x79A4, // 'f'
+    0x396B, // 'g'
+    0x5BED, // 'h'
+    0x7497, // 'i'
+    0x126A, // 'j'
+    0x5BAD, // 'k'
+    0x4927, // 'l'
+    0x5FED, // 'm'
+    0x6B6D, // 'n'
+    0x2B6A, // 'o'
+    0x6BA4, // 'p'
+    0x2B73, // 'q'
+    0x6BAD, // 'r'
+    0x388E, // 's'
+    0x7492, // 't'
+    0x5B6B, // 'u'
+    0x5B52, // 'v'
+    0x5BFD, // 'w'
+    0x5AAD, // 'x'
+    0x5A92, // 'y'
+    0x72A7, // 'z'
+    0x3513, // '{'
+    0x2492, // '|'
+    0x6456, // '}'
+    0x0780, // '~'
+];
+
+// 5x7 glyphs for ' ' to '~', one column per byte with the top pixel in bit 0
+const STANDARD: [[u8; 5]; 95] = [
+    [0x00, 0x00, 0x00, 0x00, 0x00], // ' '
+    [0x00, 0x00, 0x5F, 0x00, 0x00], // '!'
+    [0x00, 0x07, 0x00, 0x07, 0x00], // '"'
+    [0x14, 0x7F, 0x14, 0x7F, 0x14], // '#'
+    [0x24, 0x2A, 0x7F, 0x2A, 0x12], // '$'
+    [0x23, 0x13, 0x08, 0x64, 0x62], // '%'
+    [0x36, 0x49, 0x55, 0x22, 0x50], // '&'
+    [0x00, 0x05, 0x03, 0x00, 0x00], // "'"
+    [0x00, 0x1C, 0x22, 0x41, 0x00], // '('
+    [0x00, 0x41, 0x22, 0x1C, 0x00], // ')'
+    [0x08, 0x2A, 0x1C, 0x2A, 0x08], // '*'
+    [0x08, 0x08, 0x3E, 0x08, 0x08], // '+'
+    [0x00, 0x50, 0x30, 0x00, 0x00], // ','
+    [0x08, 0x08, 0x08, 0x08, 0x08], // '-'
+    [0x00, 0x60, 0x60, 0x00, 0x00], // '.'
+    [0x20, 0x10, 0x08, 0x04, 0x02], // '/'
+    [0x3E, 0x51, 0x49, 0x45, 0x3E], // '0'
+    [0x00, 0x42, 0x7F, 0x40, 0x00], // '1'
+    [0x42, 0x61, 0x51, 0x49, 0x46], // '2'
+    [0x21, 0x41, 0x45, 0x4B, 0x31], // '3'
+    [0x18, 0x14, 0x12, 0x7F, 0x10], // '4'
+    [0x27, 0x45, 0x45, 0x45, 0x39], // '5'
+    [0x3C, 0x4A, 0x49, 0x49, 0x30], // '6'
+    [0x01, 0x71, 0x09, 0x05, 0x03], // '7'
+    [0x36, 0x49, 0x49, 0x49, 0x36], // '8'
+    [0x06, 0x49, 0x49, 0x29, 0x1E], // '9'
+    [0x00, 0x36, 0x36, 0x00, 0x00], // ':'
+    [0x00, 0x56, 0x36, 0x00, 0x00], // ';'
+    [0x08, 0x14, 0x22, 0x41, 0x00], // '<'
+    [0x14, 0x14, 0x14, 0x14, 0x14], // '='
+    [0x00, 0x41, 0x22, 0x14, 0x08], // '>'
+    [0x02, 0x01, 0x51, 0x09, 0x06], // '?'
+    [0x32, 0x49, 0x79, 0x41, 0x3E], // '@'
+    [0x7E, 0x11, 0x11, 0x11, 0x7E], // 'A'
+    [0x7F, 0x49, 0x49, 0x49, 0x36], // 'B'
+    [0x3E, 0x41, 0x41, 0x41, 0x22], // 'C'
+    [0x7F, 0x41, 0x41, 0x22, 0x1C], // 'D'
+    [0x7F, 0x49, 0x49, 0x49, 0x41], // 'E'
+    [0x7F, 0x09, 0x09, 0x01, 0x01], // 'F'
+    [0x3E, 0x41, 0x41, 0x51, 0x32], // 'G'
+    [0x7F, 0x08, 0x08, 0x08, 0x7F], // 'H'
+    [0x00, 0x41, 0x7F, 0x41, 0x00], // 'I'
+    [0x20, 0x40, 0x41, 0x3F, 0x01], // 'J'
+    [0x7F, 0x08, 0x14, 0x22, 0x41], // 'K'
+    [0x7F, 0x40, 0x40, 0x40, 0x40], // 'L'
+    [0x7F, 0x02, 0x04, 0x02, 0x7F], // 'M'
+    [0x7F, 0x04, 0x08, 0x10, 0x7F], // 'N'
+    [0x3E, 0x41, 0x41, 0x41, 0x3E], // 'O'
+    [0x7F, 0x09, 0x09, 0x09, 0x06], // 'P'
+    [0x3E, 0x41, 0x51, 0x21, 0x5E], // 'Q'
+    [0x7F, 0x09, 0x19, 0x29, 0x46], // 'R'
+    [0x46, 0x49, 0x49, 0x49, 0x31], // 'S'
+    [0x01, 0x01, 0x7F, 0x01, 0x01], // 'T'
+    [0x3F, 0x40, 0x40, 0x40, 0x3F], // 'U'
+    [0x1F, 0x20, 0x40, 0x20, 0x1F], // 'V'
+    [0x7F, 0x20, 0x18, 0x20, 0x7F], // 'W'
+    [0x63, 0x14, 0x08, 0x14, 0x63], // 'X'
+    [0x03, 0x04, 0x78, 0x04, 0x03], // 'Y'
+    [0x61, 0x51, 0x49, 0x45, 0x43], // 'Z'
+    [0x00, 0x7F, 0x41, 0x41, 0x00], // '['
+    [0x02, 0x04, 0x08, 0x10, 0x20], // '\\'
+    [0x00, 0x41, 0x41, 0x7F, 0x00], // ']'
+    [0x04, 0x02, 0x01, 0x02, 0x04], // '^'
+    [0x40, 0x40, 0x40, 0x40, 0x40], // '_'
+    [0x00, 0x01, 0x02, 0x04, 0x00], // '`'
+    [0x20, 0x54, 0x54, 0x54, 0x78], // 'a'
+    [0x7F, 0x48, 0x44, 0x44, 0x38], // 'b'
+    [0x38, 0x44, 0x44, 0x44, 0x20], // 'c'
+    [0x38, 0x44, 0x44, 0x48, 0x7F], // 'd'
+    [0x38, 0x54, 0x54, 0x54, 0x18], // 'e'
+    [0x08, 0x7E, 0x09, 0x01, 0x02], // 'f'
+    [0x0C, 0x52, 0x52, 0x52, 0x3E], // 'g'
+    [0x7F, 0x08, 0x04, 0x04, 0x78], // 'h'
+    [0x00, 0x44, 0x7D, 0x40, 0x00], // 'i'
+    [0x20, 0x40, 0x44, 0x3D, 0x00], // 'j'
+    [0x7F, 0x10, 0x28, 0x44, 0x00], // 'k'
+    [0x00, 0x41, 0x7F, 0x40, 0x00], // 'l'
+    [0x7C, 0x04, 0x18, 0x04, 0x78], // 'm'
+    [0x7C, 0x08, 0x04, 0x04, 0x78], // 'n'
+    [0x38, 0x44, 0x44, 0x44, 0x38], // 'o'
+    [0x7C, 0x14, 0x14, 0x14, 0x08], // 'p'
+    [0x08, 0x14, 0x14, 0x18, 0x7C], // 'q'
+    [0x7C, 0x08, 0x04, 0x04, 0x08], // 'r'
+    [0x48, 0x54, 0x54, 0x54, 0x20], // 's'
+    [0x04, 0x3F, 0x44, 0x40, 0x20], // 't'
+    [0x3C, 0x40, 0x40, 0x20, 0x7C], // 'u'
+    [0x1C, 0x20, 0x40, 0x20, 0x1C], // 'v'
+    [0x3C, 0x40, 0x30, 0x40, 0x3C], // 'w'
+    [0x44, 0x28, 0x10, 0x28, 0x44], // 'x'
+    [0x0C, 0x50, 0x50, 0x50, 0x3C], // 'y'
+    [0x44, 0x64, 0x54, 0x4C, 0x44], // 'z'
+    [0x00, 0x08, 0x36, 0x41, 0x00], // '{'
+    [0x00, 0x00, 0x7F, 0x00, 0x00], // '|'
+    [0x00, 0x41, 0x36, 0x08, 0x00], // '}'
+    [0x08, 0x04, 0x08, 0x10, 0x08], // '~'
+];
+
+#[cfg(test)]
+mod tests {
+    use super::{Font, Glyph};
+    use crate::Canvas;
+
+    #[test]
+    fn builtin_fonts() {
+        for (font, size) in [(Font::small(), (3, 5)), (Font::standard(), (5, 7))] {
+            assert_eq!(95, font.glyphs().count());
+            for (_, glyph) in font.glyphs() {
+                assert_eq!(size, (glyph.width(), glyph.height()));
+            }
+            // Only the space is blank
+            for (c, glyph) in font.glyphs() {
+                let blank = (0..size.1).all(|y| (0..size.0).all(|x| !glyph.pixel(x, y)));
+                assert_eq!(c == ' ', blank, "{:?}", c);
+            }
+        }
+
+        let font = Font::small();
+        assert_eq!(font.glyph('A'), font.glyph('a'));
+
+        // Every other glyph can be told apart
+        for (font, uppercase_only) in [(Font::small(), true), (Font::standard(), false)] {
+            let glyphs = font
+                .glyphs()
+                .filter(|(c, _)| !(uppercase_only && c.is_ascii_lowercase()))
+                .collect::<Vec<_>>();
+            for (i, (a, glyph)) in glyphs.iter().enumerate() {
+                for (b, other) in &glyphs[i + 1..] {
+                    assert_ne!(glyph, other, "{:?} and {:?}", a, b);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn standard_glyphs() {
+        let mut canvas = Canvas::new(5, 7);
+        canvas.draw_text(0, 0, "A", &Font::standard());
+        let rows = (0..7)
+            .map(|y| {
+                (0..5)
+                    .map(|x| {
+                        if canvas.get_pixel(x, y) == Some(true) {
+                            '#'
+                        } else {
+                            '.'
+                        }
+                    })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![".###.", "#...#", "#...#", "#...#", "#####", "#...#", "#...#"],
+            rows
+        );
+    }
+
+    #[test]
+    fn fallback() {
+        let mut font = Font::small();
+        assert_eq!(font.glyph('?'), font.glyph('é'));
+
+        font.set_fallback(None);
+        assert_eq!(None, font.glyph('é'));
+        assert_eq!((3, 5), font.measure("éAé"));
+    }
+
+    #[test]
+    fn measure() {
+        let font = Font::standard();
+        assert_eq!((0, 0), font.measure(""));
+        assert_eq!((11, 7), font.measure("Hi"));
+        assert_eq!((17, 15), font.measure("Hi\nabc"));
+
+        // Offsets move the glyph without changing the advance
+        let mut font = Font::new(4);
+        font.insert(
+            '.',
+            Glyph::new(vec![true], 1, 1)
+                .with_offset(1, 2)
+                .with_advance(3),
+        );
+        assert_eq!((5, 3), font.measure(".."));
+    }
+
+    #[test]
+    fn clipping() {
+        let font = Font::small();
+
+        let mut canvas = Canvas::new(4, 4);
+        canvas.draw_text(-2, -2, "H", &font);
+        assert_eq!("⠇⠀\n", &canvas.to_string());
+
+        let mut canvas = Canvas::new(4, 4);
+        canvas.draw_text(i32::MAX, 0, "HI", &font);
+        canvas.draw_text(0, i32::MIN, "HI", &font);
+        assert_eq!("⠀⠀\n", &canvas.to_string());
+    }
+}
@@ -0,0 +1,346 @@
+// Loading X11 BDF fonts
+//
+// https://www.x.org/docs/BDF/bdf.pdf
+
+use alloc::vec::Vec;
+
+use super::{Font, FontError, Glyph};
+
+impl Font {
+    /// Load a font in the [X11 BDF][1] format.
+    ///
+    /// Glyph encodings are used as Unicode code points, which is correct for `ISO10646` and
+    /// `ISO8859-1` fonts. Glyphs without an encoding are skipped. The `DEFAULT_CHAR` property is
+    /// used as the fallback glyph.
+    ///
+    /// [1]: https://www.x.org/docs/BDF/bdf.pdf
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use braillefb::{font::Font, Canvas};
+    /// let bdf = "\
+    /// STARTFONT 2.1
+    /// FONTBOUNDINGBOX 2 2 0 0
+    /// CHARS 1
+    /// STARTCHAR x
+    /// ENCODING 120
+    /// DWIDTH 3 0
+    /// BBX 2 2 0 0
+    /// BITMAP
+    /// 80
+    /// 40
+    /// ENDCHAR
+    /// ENDFONT
+    /// ";
+    /// let font = Font::from_bdf(bdf.as_bytes()).unwrap();
+    ///
+    /// let mut canvas = Canvas::new(6, 2);
+    /// canvas.draw_text(0, 0, "xx", &font);
+    /// assert_eq!("⠑⠈⠂\n", &canvas.to_string());
+    /// ```
+    pub fn from_bdf(data: &[u8]) -> Result<Self, FontError> {
+        let text = core::str::from_utf8(data).map_err(|_| FontError::InvalidUtf8)?;
+        let mut lines = text
+            .lines()
+            .enumerate()
+            .map(|(i, line)| (i + 1, line.trim()));
+
+        let mut bounding_box = None;
+        let mut ascent = None;
+        let mut descent = None;
+        let mut default_char = None;
+        let mut default_advance = None;
+        let mut glyphs = Vec::new();
+
+        while let Some((number, line)) = lines.next() {
+            let (keyword, values) = split(line);
+            let syntax = FontError::Syntax { line: number };
+            // Metrics are kept with their line, to report values that are out of range
+            match keyword {
+                "FONTBOUNDINGBOX" => {
+                    bounding_box = Some((numbers::<4>(values).ok_or(syntax)?, number));
+                }
+                "FONT_ASCENT" => ascent = Some((numbers::<1>(values).ok_or(syntax)?[0], number)),
+                "FONT_DESCENT" => descent = Some((numbers::<1>(values).ok_or(syntax)?[0], number)),
+                "DEFAULT_CHAR" => default_char = Some(numbers::<1>(values).ok_or(syntax)?[0]),
+                "DWIDTH" => {
+                    default_advance = Some((numbers::<2>(values).ok_or(syntax)?[0], number));
+                }
+                "STARTCHAR" => glyphs.push(bdf_char(&mut lines, number)?),
+                "ENDFONT" => break,
+                _ => {}
+            }
+        }
+
+        let ([_, bb_height, _, bb_y], bb_line) =
+            bounding_box.ok_or(FontError::Missing("FONTBOUNDINGBOX"))?;
+        let syntax = |line| FontError::Syntax { line };
+        let ascent = match ascent {
+            Some(ascent) => ascent,
+            None => (bb_height.checked_add(bb_y).ok_or(syntax(bb_line))?, bb_line),
+        };
+        let descent = match descent {
+            Some(descent) => descent,
+            None => (bb_y.checked_neg().ok_or(syntax(bb_line))?, bb_line),
+        };
+        let line_height = ascent
+            .0
+            .checked_add(descent.0)
+            .and_then(|height| usize::try_from(height.max(0)).ok())
+            .ok_or(syntax(ascent.1))?;
+
+        let mut font = Font::new(line_height);
+        for c in glyphs {
+            let Some(ch) = c
+                .encoding
+                .and_then(|e| u32::try_from(e).ok())
+                .and_then(char::from_u32)
+            else {
+                continue;
+            };
+            let [width, height, x, y] = c.bounding_box;
+            let x = i32::try_from(x).map_err(|_| syntax(c.line))?;
+            // BDF offsets are from the baseline, upwards
+            let y = ascent
+                .0
+                .checked_sub(y)
+                .and_then(|y| y.checked_sub(height))
+                .and_then(|y| i32::try_from(y).ok())
+                .ok_or(syntax(c.line))?;
+            let (advance, advance_line) = c.advance.or(default_advance).unwrap_or((width, c.line));
+            let advance = usize::try_from(advance.max(0)).map_err(|_| syntax(advance_line))?;
+
+            let glyph = Glyph::new(c.pixels, width as usize, height as usize)
+                .with_offset(x, y)
+                .with_advance(advance);
+            font.insert(ch, glyph);
+        }
+        font.set_fallback(
+            default_char
+                .and_then(|c| u32::try_from(c).ok())
+                .and_then(char::from_u32),
+        );
+
+        Ok(font)
+    }
+}
+
+struct BdfChar {
+    encoding: Option<i64>,
+    advance: Option<(i64, usize)>,
+    bounding_box: [i64; 4],
+    // The line of `BBX`
+    line: usize,
+    pixels: Vec<bool>,
+}
+
+// Parse the lines from `STARTCHAR` to `ENDCHAR`
+fn bdf_char<'a>(
+    lines: &mut (impl Iterator<Item = (usize, &'a str)> + Clone),
+    start: usize,
+) -> Result<BdfChar, FontError> {
+    let mut encoding = None;
+    let mut advance = None;
+    let mut bounding_box = None;
+
+    while let Some((number, line)) = lines.next() {
+        let (keyword, values) = split(line);
+        let syntax = FontError::Syntax { line: number };
+        match keyword {
+            // Unencoded glyphs are `ENCODING -1` optionally followed by a non-standard encoding
+            "ENCODING" => {
+                let n = values
+                    .split_whitespace()
+                    .next()
+                    .and_then(|n| n.parse().ok());
+                encoding = Some(n.ok_or(syntax)?).filter(|&n: &i64| n >= 0);
+            }
+            "DWIDTH" => advance = Some((numbers::<2>(values).ok_or(syntax)?[0], number)),
+            "BBX" => {
+                let bbx = numbers::<4>(values).ok_or(syntax)?;
+                if bbx[0] < 0 || bbx[1] < 0 {
+                    return Err(syntax);
+                }
+                bounding_box = Some((bbx, number));
+            }
+            "BITMAP" => {
+                let (bounding_box, line) = bounding_box.ok_or(syntax)?;
+                let size = |n: i64| usize::try_from(n).map_err(|_| syntax);
+                let (width, height) = (size(bounding_box[0])?, size(bounding_box[1])?);
+                let len = width.checked_mul(height).ok_or(syntax)?;
+                // Only trust the bounding box with an allocation once the rows are there
+                let mut rows = lines.clone().take(height);
+                if rows.clone().count() < height {
+                    return Err(FontError::UnexpectedEof);
+                }
+                let wide_enough = rows.all(|(_, row)| row.len() * 4 >= width);
+                let mut pixels = Vec::with_capacity(if wide_enough { len } else { 0 });
+                for _ in 0..height {
+                    let (number, row) = lines.next().ok_or(FontError::UnexpectedEof)?;
+                    let bytes = hex(row).ok_or(FontError::Syntax { line: number })?;
+                    if bytes.len() * 8 < width {
+                        return Err(FontError::Syntax { line: number });
+                    }
+                    pixels.extend((0..width).map(|x| bytes[x / 8] & (0x80 >> (x % 8)) != 0));
+                }
+
+                let (number, end) = lines.next().ok_or(FontError::UnexpectedEof)?;
+                if end != "ENDCHAR" {
+                    return Err(FontError::Syntax { line: number });
+                }
+                return Ok(BdfChar {
+                    encoding,
+                    advance,
+                    bounding_box,
+                    line,
+                    pixels,
+                });
+            }
+            "ENDCHAR" => return Err(FontError::Syntax { line: start }),
+            _ => {}
+        }
+    }
+
+    Err(FontError::UnexpectedEof)
+}
+
+fn split(line: &str) -> (&str, &str) {
+    line.split_once(char::is_whitespace)
+        .map(|(keyword, values)| (keyword, values.trim()))
+        .unwrap_or((line, ""))
+}
+
+// Parse exactly `N` whitespace separated integers
+fn numbers<const N: usize>(values: &str) -> Option<[i64; N]> {
+    let mut numbers = [0; N];
+    let mut values = values.split_whitespace();
+    for n in &mut numbers {
+        *n = values.next()?.parse().ok()?;
+    }
+    values.next().is_none().then_some(numbers)
+}
+
+fn hex(row: &str) -> Option<Vec<u8>> {
+    if row.len() % 2 != 0 || !row.is_ascii() {
+        return None;
+    }
+    (0..row.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&row[i..i + 2], 16).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::font::{Font, FontError};
+
+    const BDF: &str = "\
+STARTFONT 2.1
+FONT -test-medium-r-normal--4-40-75-75-c-40-iso10646-1
+SIZE 4 75 75
+FONTBOUNDINGBOX 3 4 0 -1
+STARTPROPERTIES 3
+FONT_ASCENT 3
+FONT_DESCENT 1
+DEFAULT_CHAR 65
+ENDPROPERTIES
+CHARS 3
+STARTCHAR A
+ENCODING 65
+SWIDTH 1000 0
+DWIDTH 4 0
+BBX 3 3 0 0
+BITMAP
+40
+A0
+E0
+ENDCHAR
+STARTCHAR comma
+ENCODING 44
+DWIDTH 2 0
+BBX 1 2 0 -1
+BITMAP
+80
+80
+ENDCHAR
+STARTCHAR unencoded
+ENCODING -1 200
+DWIDTH 2 0
+BBX 1 1 0 0
+BITMAP
+80
+ENDCHAR
+ENDFONT
+";
+
+    #[test]
+    fn glyphs() {
+        let font = Font::from_bdf(BDF.as_bytes()).unwrap();
+        assert_eq!(4, font.line_height());
+        assert_eq!(2, font.glyphs().count());
+
+        let a = font.glyph('A').unwrap();
+        assert_eq!(
+            (3, 3, (0, 0), 4),
+            (a.width(), a.height(), a.offset(), a.advance())
+        );
+        assert!(a.pixel(1, 0) && !a.pixel(0, 0) && a.pixel(0, 2));
+
+        // Below the baseline
+        let comma = font.glyph(',').unwrap();
+        assert_eq!((0, 2), comma.offset());
+
+        // DEFAULT_CHAR
+        assert_eq!(Some(a), font.glyph('z'));
+        assert_eq!((9, 4), font.measure("A,A"));
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            Err(FontError::Syntax { line: 18 }),
+            Font::from_bdf(BDF.replace("A0", "A").as_bytes())
+        );
+        assert_eq!(
+            Err(FontError::UnexpectedEof),
+            Font::from_bdf(&BDF.as_bytes()[..BDF.find("E0").unwrap()])
+        );
+        assert_eq!(Err(FontError::InvalidUtf8), Font::from_bdf(&[0xff]));
+
+        // An oversized bounding box without the rows to back it
+        let huge = "STARTFONT 2.1\nFONTBOUNDINGBOX 1 1 0 0\nSTARTCHAR x\nENCODING 120\n\
+            BBX 4000000000 4000000000 0 0\nBITMAP\n00\nENDCHAR\nENDFONT\n";
+        assert_eq!(
+            Err(FontError::UnexpectedEof),
+            Font::from_bdf(huge.as_bytes())
+        );
+        let huge = huge.replace("4000000000 4000000000", "4000000000000 4000000000000");
+        assert_eq!(
+            Err(FontError::Syntax { line: 6 }),
+            Font::from_bdf(huge.as_bytes())
+        );
+
+        // Metrics that overflow
+        let max = BDF.replace("FONT_ASCENT 3", "FONT_ASCENT 9223372036854775807");
+        assert_eq!(
+            Err(FontError::Syntax { line: 6 }),
+            Font::from_bdf(max.as_bytes())
+        );
+        let min = "STARTFONT 2.1\nFONTBOUNDINGBOX 1 1 0 -9223372036854775808\nENDFONT\n";
+        assert_eq!(
+            Err(FontError::Syntax { line: 2 }),
+            Font::from_bdf(min.as_bytes())
+        );
+        let far = BDF.replace("BBX 3 3 0 0", "BBX 3 3 4000000000 0");
+        assert_eq!(
+            Err(FontError::Syntax { line: 15 }),
+            Font::from_bdf(far.as_bytes())
+        );
+        let far = BDF.replace("BBX 3 3 0 0", "BBX 3 3 0 -4000000000");
+        assert_eq!(
+            Err(FontError::Syntax { line: 15 }),
+            Font::from_bdf(far.as_bytes())
+        );
+    }
+}
@@ -0,0 +1,263 @@
+// Loading Linux console PSF1 and PSF2 fonts
+//
+// https://www.win.tue.nl/~aeb/linux/kbd/font-formats-1.html
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use super::{Font, FontError, Glyph};
+
+const PSF1_MAGIC: [u8; 2] = [0x36, 0x04];
+const PSF1_MODE512: u8 = 0x01;
+const PSF1_MODEHASTAB: u8 = 0x02;
+const PSF1_MODESEQ: u8 = 0x04;
+const PSF1_SEPARATOR: u16 = 0xffff;
+const PSF1_STARTSEQ: u16 = 0xfffe;
+
+const PSF2_MAGIC: [u8; 4] = [0x72, 0xb5, 0x4a, 0x86];
+const PSF2_HAS_UNICODE_TABLE: u32 = 0x01;
+const PSF2_SEPARATOR: u8 = 0xff;
+const PSF2_STARTSEQ: u8 = 0xfe;
+
+impl Font {
+    /// Load a Linux console font in the [PSF1 or PSF2][1] format.
+    ///
+    /// If the font has a Unicode table, each glyph is mapped to all of its `char`s. Sequences of
+    /// `char`s (like a letter followed by a combining accent) can't be drawn and are skipped.
+    /// Without a table, glyphs are mapped to the `char`s with the same code point as their
+    /// position in the font.
+    ///
+    /// [1]: https://www.win.tue.nl/~aeb/linux/kbd/font-formats-1.html
+    pub fn from_psf(data: &[u8]) -> Result<Self, FontError> {
+        if data.starts_with(&PSF1_MAGIC) {
+            psf1(data)
+        } else if data.starts_with(&PSF2_MAGIC) {
+            psf2(data)
+        } else {
+            Err(FontError::InvalidMagic)
+        }
+    }
+}
+
+fn psf1(data: &[u8]) -> Result<Font, FontError> {
+    let (mode, height) = match data {
+        [_, _, mode, height, ..] => (*mode, *height as usize),
+        _ => return Err(FontError::UnexpectedEof),
+    };
+    let count = if mode & PSF1_MODE512 != 0 { 512 } else { 256 };
+    let glyphs = bitmaps(&data[4..], count, 8, height)?;
+
+    let mut chars = vec![Vec::new(); count];
+    if mode & (PSF1_MODEHASTAB | PSF1_MODESEQ) != 0 {
+        let table = &data[4 + count * height..];
+        let mut entries = table
+            .chunks_exact(2)
+            .map(|pair| u16::from_le_bytes([pair[0], pair[1]]));
+        for chars in &mut chars {
+            let mut sequence = false;
+            loop {
+                match entries.next().ok_or(FontError::UnexpectedEof)? {
+                    PSF1_SEPARATOR => break,
+                    PSF1_STARTSEQ => sequence = true,
+                    c if !sequence => chars.extend(char::from_u32(c as u32)),
+                    _ => {}
+                }
+            }
+        }
+    } else {
+        identity(&mut chars);
+    }
+
+    Ok(font(glyphs, chars, 8, height))
+}
+
+fn psf2(data: &[u8]) -> Result<Font, FontError> {
+    let header = |i: usize| {
+        data.get(i * 4..i * 4 + 4)
+            .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]) as usize)
+            .ok_or(FontError::UnexpectedEof)
+    };
+    let header_size = header(2)?;
+    let flags = header(3)? as u32;
+    let count = header(4)?;
+    let glyph_size = header(5)?;
+    let height = header(6)?;
+    let width = header(7)?;
+
+    // Empty glyphs take no data, so any glyph count would fit
+    if header_size < 32
+        || width == 0
+        || height == 0
+        || width.div_ceil(8).checked_mul(height) != Some(glyph_size)
+    {
+        return Err(FontError::InvalidHeader);
+    }
+    let glyph_data = data.get(header_size..).ok_or(FontError::UnexpectedEof)?;
+    let glyphs = bitmaps(glyph_data, count, width, height)?;
+
+    let mut chars = vec![Vec::new(); count];
+    if flags & PSF2_HAS_UNICODE_TABLE != 0 {
+        let mut table = &glyph_data[count * glyph_size..];
+        for chars in &mut chars {
+            let end = table
+                .iter()
+                .position(|&b| b == PSF2_SEPARATOR)
+                .ok_or(FontError::UnexpectedEof)?;
+            let entry = &table[..end];
+            table = &table[end + 1..];
+
+            // Single `char`s come before any sequences
+            let singles = match entry.iter().position(|&b| b == PSF2_STARTSEQ) {
+                Some(start) => &entry[..start],
+                None => entry,
+            };
+            let singles = core::str::from_utf8(singles).map_err(|_| FontError::InvalidUtf8)?;
+            chars.extend(singles.chars());
+        }
+    } else {
+        identity(&mut chars);
+    }
+
+    Ok(font(glyphs, chars, width, height))
+}
+
+// Split `data` into `count` glyphs, each row padded to a whole number of bytes
+fn bitmaps(
+    data: &[u8],
+    count: usize,
+    width: usize,
+    height: usize,
+) -> Result<Vec<Vec<bool>>, FontError> {
+    let row_size = width.div_ceil(8);
+    let glyph_size = row_size * height;
+    let data = count
+        .checked_mul(glyph_size)
+        .and_then(|len| data.get(..len))
+        .ok_or(FontError::UnexpectedEof)?;
+
+    Ok((0..count)
+        .map(|i| {
+            let glyph = &data[i * glyph_size..(i + 1) * glyph_size];
+            (0..width * height)
+                .map(|p| {
+                    let (x, y) = (p % width, p / width);
+                    glyph[y * row_size + x / 8] & (0x80 >> (x % 8)) != 0
+                })
+                .collect()
+        })
+        .collect())
+}
+
+fn identity(chars: &mut [Vec<char>]) {
+    for (i, chars) in chars.iter_mut().enumerate() {
+        chars.extend(char::from_u32(i as u32));
+    }
+}
+
+fn font(glyphs: Vec<Vec<bool>>, chars: Vec<Vec<char>>, width: usize, height: usize) -> Font {
+    let mut font = Font::new(height);
+    for (pixels, chars) in glyphs.into_iter().zip(chars) {
+        let glyph = Glyph::new(pixels, width, height);
+        for c in chars {
+            font.insert(c, glyph.clone());
+        }
+    }
+    font
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PSF1_MAGIC, PSF2_MAGIC};
+    use crate::font::{Font, FontError};
+
+    // A 256 glyph PSF1 font where glyph `i` has row `i % 8` set
+    fn psf1(table: Option<&[u16]>) -> Vec<u8> {
+        let mode = if table.is_some() { 0x02 } else { 0 };
+        let mut data = vec![PSF1_MAGIC[0], PSF1_MAGIC[1], mode, 8];
+        for i in 0..256 {
+            data.extend((0..8).map(|row| if row == i % 8 { 0xff } else { 0 }));
+        }
+        for entry in table.into_iter().flatten() {
+            data.extend(entry.to_le_bytes());
+        }
+        data
+    }
+
+    #[test]
+    fn psf1_fonts() {
+        let font = Font::from_psf(&psf1(None)).unwrap();
+        assert_eq!(256, font.glyphs().count());
+        assert_eq!(8, font.line_height());
+        let glyph = font.glyph('A').unwrap();
+        assert_eq!((8, 8, 8), (glyph.width(), glyph.height(), glyph.advance()));
+        assert!(glyph.pixel(0, 1) && !glyph.pixel(0, 0));
+
+        // Glyph 1 is 'x' and 'y', glyph 2 is only a sequence, the rest are unmapped
+        let mut table = vec![
+            0xffff, 'x' as u16, 'y' as u16, 0xffff, 0xfffe, 'e' as u16, 0x301,
+        ];
+        table.extend([0xffff; 254]);
+        let font = Font::from_psf(&psf1(Some(&table))).unwrap();
+        assert_eq!(
+            vec!['x', 'y'],
+            font.glyphs().map(|(c, _)| c).collect::<Vec<_>>()
+        );
+        assert!(font.glyph('x').unwrap().pixel(0, 1));
+    }
+
+    #[test]
+    fn psf2_fonts() {
+        // Two 10x2 glyphs, rows are padded to 2 bytes
+        let mut data = PSF2_MAGIC.to_vec();
+        for n in [0, 32, 1, 2, 4, 2, 10] {
+            data.extend((n as u32).to_le_bytes());
+        }
+        data.extend([0xc0, 0x40, 0, 0, 0, 0, 0x80, 0]);
+        // Glyph 0 is 'a' and 'b' and the sequence "c\u{301}"
+        data.extend(b"ab\xfec\xcc\x81\xff");
+        data.extend("é".as_bytes());
+        data.push(0xff);
+
+        let font = Font::from_psf(&data).unwrap();
+        assert_eq!(
+            vec!['a', 'b', 'é'],
+            font.glyphs().map(|(c, _)| c).collect::<Vec<_>>()
+        );
+        let a = font.glyph('a').unwrap();
+        assert_eq!((10, 2), (a.width(), a.height()));
+        assert!(a.pixel(0, 0) && a.pixel(1, 0) && a.pixel(9, 0) && !a.pixel(2, 0));
+        assert!(font.glyph('é').unwrap().pixel(0, 1));
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(Err(FontError::InvalidMagic), Font::from_psf(b"not a font"));
+        assert_eq!(
+            Err(FontError::UnexpectedEof),
+            Font::from_psf(&psf1(None)[..100])
+        );
+        assert_eq!(
+            Err(FontError::UnexpectedEof),
+            Font::from_psf(&psf1(Some(&[0xffff])))
+        );
+
+        let mut data = PSF2_MAGIC.to_vec();
+        for n in [0, 32, 0, 1, 3, 2, 10] {
+            data.extend((n as u32).to_le_bytes());
+        }
+        assert_eq!(Err(FontError::InvalidHeader), Font::from_psf(&data));
+
+        // 200 million empty glyphs
+        let mut data = PSF2_MAGIC.to_vec();
+        for n in [0, 32, 0, 200_000_000, 0, 0, 0] {
+            data.extend((n as u32).to_le_bytes());
+        }
+        assert_eq!(Err(FontError::InvalidHeader), Font::from_psf(&data));
+        data[8] = 16;
+        data[20..28].copy_from_slice(&[1, 0, 0, 0, 1, 0, 0, 0]);
+        data[28] = 8;
+        assert_eq!(Err(FontError::InvalidHeader), Font::from_psf(&data));
+        data[8] = 32;
+        assert_eq!(Err(FontError::UnexpectedEof), Font::from_psf(&data));
+    }
+}
@@ -0,0 +1,290 @@
+use crate::{PixelSource, CHARS};
+
+/// The `char`s used to render pixels.
+///
+/// Braille is supported by the most fonts, but many fonts draw braille dots with gaps between
+/// them. The block element sets fill the whole cell, at the cost of a lower resolution or less
+/// font support.
+///
+/// | Set | Pixels per `char` | Range |
+/// |-----|-------------------|-------|
+/// | [`Braille`](GlyphSet::Braille) | 2x4 | U+2800 to U+28FF |
+/// | [`Octant`](GlyphSet::Octant) | 2x4 | U+1CD00 to U+1CDE5 (Unicode 16) |
+/// | [`Sextant`](GlyphSet::Sextant) | 2x3 | U+1FB00 to U+1FB3B |
+/// | [`Quadrant`](GlyphSet::Quadrant) | 2x2 | U+2596 to U+259F |
+/// | [`HalfBlock`](GlyphSet::HalfBlock) | 1x2 | U+2580 and U+2584 |
+/// | [`Ascii`](GlyphSet::Ascii) | 2x4 | ` .:-=+*#@` |
+/// | [`AsciiShapes`](GlyphSet::AsciiShapes) | 2x4 | `"-_\|/\[]L=o.#` |
+///
+/// Combinations that already exist as block elements (like `▌` or `█`) use those `char`s. The
+/// ASCII sets are for terminals and logs that can't display any of the others, they only
+/// approximate the pixels.
+///
+/// # Example
+///
+/// ```
+/// # use braillefb::{Framebuffer, GlyphSet};
+/// // A 2x2 square in the top-left corner
+/// let pixels = [
+///     true, true, false, false,
+///     true, true, false, false,
+///     false, false, false, false,
+///     false, false, false, false,
+/// ];
+/// let f = Framebuffer::new(&pixels, 4, 4);
+///
+/// assert_eq!("⠛⠀\n", &f.to_string());
+/// assert_eq!("▀ \n", &f.glyph_set(GlyphSet::Octant).to_string());
+/// assert_eq!("🬎 \n  \n", &f.glyph_set(GlyphSet::Sextant).to_string());
+/// assert_eq!("█ \n  \n", &f.glyph_set(GlyphSet::Quadrant).to_string());
+/// assert_eq!("██  \n    \n", &f.glyph_set(GlyphSet::HalfBlock).to_string());
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub enum GlyphSet {
+    /// Braille patterns.
+    #[default]
+    Braille,
+    /// Block octants.
+    Octant,
+    /// Block sextants.
+    Sextant,
+    /// Quadrant block elements.
+    Quadrant,
+    /// Upper and lower half blocks.
+    HalfBlock,
+    /// ASCII `char`s picked by how many dots are set, from ` ` to `@`.
+    Ascii,
+    /// ASCII `char`s picked by the shape of the set dots, like `/` for a diagonal line.
+    AsciiShapes,
+}
+
+impl GlyphSet {
+    /// The width and height in pixels covered by each `char`.
+    pub fn cell_size(self) -> (usize, usize) {
+        match self {
+            GlyphSet::Braille | GlyphSet::Octant | GlyphSet::Ascii | GlyphSet::AsciiShapes => {
+                (2, 4)
+            }
+            GlyphSet::Sextant => (2, 3),
+            GlyphSet::Quadrant => (2, 2),
+            GlyphSet::HalfBlock => (1, 2),
+        }
+    }
+
+    // The `char` for the cell at `col`, `row`
+    pub(crate) fn char_at<S: PixelSource>(
+        self,
+        source: &S,
+        col: usize,
+        row: usize,
+    ) -> &'static char {
+        match self {
+            GlyphSet::Braille => &CHARS[source.cell(col, row) as usize],
+            GlyphSet::Octant => &OCTANTS[source.cell(col, row) as usize],
+            GlyphSet::Sextant => &SEXTANTS[self.row_major(source, col, row)],
+            GlyphSet::Quadrant => &QUADRANTS[self.row_major(source, col, row)],
+            GlyphSet::HalfBlock => &HALF_BLOCKS[self.row_major(source, col, row)],
+            GlyphSet::Ascii => &ASCII[source.cell(col, row) as usize],
+            GlyphSet::AsciiShapes => &ASCII_SHAPES[source.cell(col, row) as usize],
+        }
+    }
+
+    // Packs the pixels of a cell with the top-left pixel in bit 0, in row-major order
+    fn row_major<S: PixelSource>(self, source: &S, col: usize, row: usize) -> usize {
+        let (width, height) = self.cell_size();
+        let (x_offset, y_offset) = (col * width, row * height);
+        let mut cell = 0;
+        for y in 0..height {
+            for x in 0..width {
+                let (x, y) = (x_offset + x, y_offset + y);
+                if x < source.width() && y < source.height() && source.pixel(x, y) {
+                    cell |= 1 << (x - x_offset + (y - y_offset) * width);
+                }
+            }
+        }
+        cell
+    }
+}
+
+// Indexed by the same bit-packed cells as `CHARS`. Octants that look like an existing block
+// element use that `char` instead, as Unicode doesn't duplicate them.
+const OCTANTS: [char; 256] = [
+    ' ', '𜺨', '𜴀', '▘', '𜴉', '𜴊', '🯦', '𜴍', '𜺫', '🮂', '𜴁', '𜴂', '𜴋', '𜴌', '𜴎', '𜴏', '𜴃', '𜴄', '𜴆',
+    '𜴇', '𜴐', '𜴑', '𜴔', '𜴕', '▝', '𜴅', '𜴈', '▀', '𜴒', '𜴓', '𜴖', '𜴗', '𜴘', '𜴙', '𜴜', '𜴝', '𜴧', '𜴨',
+    '𜴫', '𜴬', '𜴚', '𜴛', '𜴞', '𜴟', '𜴩', '𜴪', '𜴭', '𜴮', '🯧', '𜴠', '𜴣', '𜴤', '𜴯', '𜴰', '𜴳', '𜴴', '𜴡',
+    '𜴢', '𜴥', '𜴦', '𜴱', '𜴲', '𜴵', '🮅', '𜺣', '𜴶', '𜴹', '𜴺', '▖', '𜵅', '𜵈', '▌', '𜴷', '𜴸', '𜴻', '𜴼',
+    '𜵆', '𜵇', '𜵉', '𜵊', '𜴽', '𜴾', '𜵁', '𜵂', '𜵋', '𜵌', '𜵎', '𜵏', '𜴿', '𜵀', '𜵃', '𜵄', '▞', '𜵍', '𜵐',
+    '▛', '𜵑', '𜵒', '𜵕', '𜵖', '𜵡', '𜵢', '𜵥', '𜵦', '𜵓', '𜵔', '𜵗', '𜵘', '𜵣', '𜵤', '𜵧', '𜵨', '𜵙', '𜵚',
+    '𜵝', '𜵞', '𜵩', '𜵪', '𜵭', '𜵮', '𜵛', '𜵜', '𜵟', '𜵠', '𜵫', '𜵬', '𜵯', '𜵰', '𜺠', '𜵱', '𜵴', '𜵵', '𜶀',
+    '𜶁', '𜶄', '𜶅', '𜵲', '𜵳', '𜵶', '𜵷', '𜶂', '𜶃', '𜶆', '𜶇', '𜵸', '𜵹', '𜵼', '𜵽', '𜶈', '𜶉', '𜶌', '𜶍',
+    '𜵺', '𜵻', '𜵾', '𜵿', '𜶊', '𜶋', '𜶎', '𜶏', '▗', '𜶐', '𜶓', '▚', '𜶜', '𜶝', '𜶠', '𜶡', '𜶑', '𜶒', '𜶔',
+    '𜶕', '𜶞', '𜶟', '𜶢', '𜶣', '𜶖', '𜶗', '𜶙', '𜶚', '𜶤', '𜶥', '𜶨', '𜶩', '▐', '𜶘', '𜶛', '▜', '𜶦', '𜶧',
+    '𜶪', '𜶫', '▂', '𜶬', '𜶯', '𜶰', '𜶻', '𜶼', '𜶿', '𜷀', '𜶭', '𜶮', '𜶱', '𜶲', '𜶽', '𜶾', '𜷁', '𜷂', '𜶳',
+    '𜶴', '𜶷', '𜶸', '𜷃', '𜷄', '𜷇', '𜷈', '𜶵', '𜶶', '𜶹', '𜶺', '𜷅', '𜷆', '𜷉', '𜷊', '𜷋', '𜷌', '𜷏', '𜷐',
+    '▄', '𜷛', '𜷞', '▙', '𜷍', '𜷎', '𜷑', '𜷒', '𜷜', '𜷝', '𜷟', '𜷠', '𜷓', '𜷔', '𜷗', '𜷘', '𜷡', '𜷢', '▆',
+    '𜷤', '𜷕', '𜷖', '𜷙', '𜷚', '▟', '𜷣', '𜷥', '█',
+];
+
+// Indexed by the dots in row-major order, bit 0 is the top-left dot
+const SEXTANTS: [char; 64] = [
+    ' ', '🬀', '🬁', '🬂', '🬃', '🬄', '🬅', '🬆', '🬇', '🬈', '🬉', '🬊', '🬋', '🬌', '🬍', '🬎', '🬏', '🬐', '🬑',
+    '🬒', '🬓', '▌', '🬔', '🬕', '🬖', '🬗', '🬘', '🬙', '🬚', '🬛', '🬜', '🬝', '🬞', '🬟', '🬠', '🬡', '🬢', '🬣',
+    '🬤', '🬥', '🬦', '🬧', '▐', '🬨', '🬩', '🬪', '🬫', '🬬', '🬭', '🬮', '🬯', '🬰', '🬱', '🬲', '🬳', '🬴', '🬵',
+    '🬶', '🬷', '🬸', '🬹', '🬺', '🬻', '█',
+];
+
+const QUADRANTS: [char; 16] = [
+    ' ', '▘', '▝', '▀', '▖', '▌', '▞', '▛', '▗', '▚', '▐', '▜', '▄', '▙', '▟', '█',
+];
+
+const HALF_BLOCKS: [char; 4] = [' ', '▀', '▄', '█'];
+
+// Indexed by the same bit-packed cells as `CHARS`
+const ASCII: [char; 256] = ascii();
+const ASCII_SHAPES: [char; 256] = ascii_shapes();
+
+// One `char` for each number of set dots
+const ASCII_RAMP: [char; 9] = [' ', '.', ':', '-', '=', '+', '*', '#', '@'];
+
+// The dots that each `char` roughly covers, as bit-packed cells. Some `char`s are listed more than
+// once to match different positions.
+const ASCII_TEMPLATES: [(char, u8); 21] = [
+    (' ', 0b0000_0000),
+    ('.', 0b0100_0000),
+    ('.', 0b1000_0000),
+    ('_', 0b1100_0000),
+    ('-', 0b0001_0010),
+    ('-', 0b0010_0100),
+    ('=', 0b0011_0110),
+    ('"', 0b0000_1001),
+    ('"', 0b0001_1011),
+    ('o', 0b1110_0100),
+    ('|', 0b0100_0111),
+    ('|', 0b1011_1000),
+    ('/', 0b0101_1100),
+    ('/', 0b0000_1010),
+    ('/', 0b0110_0000),
+    ('\\', 0b1010_0011),
+    ('\\', 0b0001_0001),
+    ('\\', 0b1000_0100),
+    ('L', 0b1100_0111),
+    ('[', 0b1100_1111),
+    (']', 0b1111_1001),
+];
+
+const fn ascii() -> [char; 256] {
+    let mut chars = [' '; 256];
+    let mut cell = 0;
+    while cell < 256 {
+        let dots = (cell as u8).count_ones() as usize;
+        chars[cell] = ASCII_RAMP[dots];
+        cell += 1;
+    }
+    chars
+}
+
+// Picks the template with the fewest differing dots, or `#` for mostly set cells
+const fn ascii_shapes() -> [char; 256] {
+    let mut chars = [' '; 256];
+    let mut cell = 0;
+    while cell < 256 {
+        if (cell as u8).count_ones() >= 7 {
+            chars[cell] = '#';
+        } else {
+            let mut best = 0;
+            let mut i = 1;
+            while i < ASCII_TEMPLATES.len() {
+                let distance = (cell as u8 ^ ASCII_TEMPLATES[i].1).count_ones();
+                if distance < (cell as u8 ^ ASCII_TEMPLATES[best].1).count_ones() {
+                    best = i;
+                }
+                i += 1;
+            }
+            chars[cell] = ASCII_TEMPLATES[best].0;
+        }
+        cell += 1;
+    }
+    chars
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{GlyphSet, ASCII, ASCII_RAMP, ASCII_SHAPES, OCTANTS, SEXTANTS};
+    use crate::Framebuffer;
+
+    #[test]
+    fn octants() {
+        // Every octant is used once
+        let mut chars = OCTANTS.to_vec();
+        chars.sort();
+        chars.dedup();
+        assert_eq!(256, chars.len());
+
+        assert_eq!(' ', OCTANTS[0]);
+        assert_eq!('█', OCTANTS[0xff]);
+        assert_eq!('▌', OCTANTS[0b0100_0111]);
+        assert_eq!('▄', OCTANTS[0b1110_0100]);
+    }
+
+    #[test]
+    fn sextants() {
+        assert_eq!('🬀', SEXTANTS[1]);
+        assert_eq!('▌', SEXTANTS[0b010101]);
+        assert_eq!('🬻', SEXTANTS[62]);
+        // Every sextant is used once
+        let mut chars = SEXTANTS.to_vec();
+        chars.sort();
+        chars.dedup();
+        assert_eq!(64, chars.len());
+    }
+
+    #[test]
+    fn cell_sizes() {
+        // 5x5 pixels with a diagonal line
+        let f = Framebuffer::from_fn(5, 5, |x, y| x == y);
+
+        let f = f.glyph_set(GlyphSet::Sextant);
+        assert_eq!((4, 2), (f.x_chars_count(), f.y_chars_count()));
+        assert_eq!("🬈🬏 \n 🬁🬃\n", &f.to_string());
+
+        let f = f.glyph_set(GlyphSet::Quadrant);
+        assert_eq!((4, 3), (f.x_chars_count(), f.y_chars_count()));
+        assert_eq!("▚  \n ▚ \n  ▘\n", &f.to_string());
+
+        let f = f.glyph_set(GlyphSet::HalfBlock);
+        assert_eq!((6, 3), (f.x_chars_count(), f.y_chars_count()));
+        assert_eq!(f.len(), f.to_string().chars().count());
+        assert_eq!("▀▄   \n  ▀▄ \n    ▀\n", &f.to_string());
+        assert_eq!(Some('▄'), f.get(1));
+        assert_eq!(Some('\n'), f.get(5));
+        assert_eq!(None, f.get(18));
+    }
+
+    #[test]
+    fn ascii() {
+        assert_eq!(' ', ASCII[0]);
+        assert_eq!('.', ASCII[0b0000_1000]);
+        assert_eq!('=', ASCII[0b0000_1111]);
+        assert_eq!('@', ASCII[0xff]);
+        // Every `char` of the ramp is used
+        for (dots, &c) in ASCII_RAMP.iter().enumerate() {
+            assert_eq!(c, ASCII[(1u16 << dots) as usize - 1]);
+        }
+
+        assert_eq!(' ', ASCII_SHAPES[0]);
+        assert_eq!('#', ASCII_SHAPES[0xff]);
+        assert_eq!('_', ASCII_SHAPES[0b1100_0000]);
+        // Close to a template
+        assert_eq!('|', ASCII_SHAPES[0b0000_0111]);
+        assert_eq!('/', ASCII_SHAPES[0b0101_1000]);
+
+        // The same lines as braille
+        let f = Framebuffer::from_fn(8, 8, |x, y| x == y);
+        assert_eq!("⠑⢄⠀⠀\n⠀⠀⠑⢄\n", f.to_string());
+        assert_eq!("::  \n  ::\n", f.glyph_set(GlyphSet::Ascii).to_string());
+        assert_eq!(
+            "\\\\  \n  \\\\\n",
+            f.glyph_set(GlyphSet::AsciiShapes).to_string()
+        );
+    }
+}
@@ -0,0 +1,284 @@
+//! Converting images loaded with the [`image`][1] crate.
+//!
+//! [`Converter`] resizes a [`DynamicImage`] to fit a number of terminal cells, correcting for
+//! cells being taller than they are wide, converts it to grayscale and turns it into dots with a
+//! [`Dither`].
+//!
+//! # Example
+//!
+//! ```no_run
+//! # use braillefb::{dither::{Dither, Method}, image::Converter};
+//! let image = image::open("screenshot.png").unwrap();
+//! let canvas = Converter::new()
+//!     .width(80)
+//!     .dither(Dither::new(Method::Atkinson))
+//!     .apply(&image);
+//!
+//! print!("{}", canvas);
+//! ```
+//!
+//! [1]: https://docs.rs/image
+
+use image::imageops::FilterType;
+use image::DynamicImage;
+
+use crate::dither::{Dither, Method};
+use crate::{Canvas, GlyphSet};
+
+/// Converts a [`DynamicImage`] into a [`Canvas`].
+///
+/// By default the image is kept at its own width in pixels, and thresholded with
+/// [`Method::Otsu`].
+#[derive(Debug, Copy, Clone)]
+pub struct Converter {
+    cols: Option<usize>,
+    rows: Option<usize>,
+    glyph_set: GlyphSet,
+    cell_aspect: f64,
+    preserve_aspect: bool,
+    filter: FilterType,
+    dither: Dither,
+    invert: bool,
+}
+
+impl Converter {
+    /// Create a converter with the default options.
+    pub fn new() -> Self {
+        Self {
+            cols: None,
+            rows: None,
+            glyph_set: GlyphSet::Braille,
+            cell_aspect: 2.0,
+            preserve_aspect: true,
+            filter: FilterType::Triangle,
+            dither: Dither::new(Method::Otsu),
+            invert: false,
+        }
+    }
+
+    /// Resize the image to be `cols` cells wide. Without a height, the height follows from the
+    /// aspect ratio of the image.
+    pub fn width(mut self, cols: usize) -> Self {
+        self.cols = Some(cols);
+        self
+    }
+
+    /// Resize the image to be `rows` cells tall. Without a width, the width follows from the
+    /// aspect ratio of the image.
+    pub fn height(mut self, rows: usize) -> Self {
+        self.rows = Some(rows);
+        self
+    }
+
+    /// Set the glyph set the canvas will be rendered with, which sets the number of pixels in a
+    /// cell. Defaults to [`GlyphSet::Braille`].
+    ///
+    /// The canvas has to be rendered with the same glyph set, see [`Framebuffer::glyph_set`].
+    ///
+    /// [`Framebuffer::glyph_set`]: crate::Framebuffer::glyph_set
+    pub fn glyph_set(mut self, glyph_set: GlyphSet) -> Self {
+        self.glyph_set = glyph_set;
+        self
+    }
+
+    /// Set the height of a terminal cell divided by its width, which depends on the font.
+    /// Defaults to 2.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `cell_aspect` is not a finite number larger than 0.
+    pub fn cell_aspect(mut self, cell_aspect: f64) -> Self {
+        assert!(
+            cell_aspect.is_finite() && cell_aspect > 0.0,
+            "cell aspect must be finite and positive"
+        );
+        self.cell_aspect = cell_aspect;
+        self
+    }
+
+    /// Set whether the aspect ratio of the image is kept when both a width and a height are set.
+    /// When it is, the image is as large as possible while fitting in both. Defaults to `true`.
+    pub fn preserve_aspect(mut self, preserve_aspect: bool) -> Self {
+        self.preserve_aspect = preserve_aspect;
+        self
+    }
+
+    /// Set the filter used to resize the image. Defaults to [`FilterType::Triangle`].
+    pub fn filter(mut self, filter: FilterType) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    /// Set how the grayscale image is converted into dots.
+    pub fn dither(mut self, dither: Dither) -> Self {
+        self.dither = dither;
+        self
+    }
+
+    /// Set dark pixels instead of light ones, for terminals with a light background.
+    pub fn invert(mut self, invert: bool) -> Self {
+        self.invert = invert;
+        self
+    }
+
+    /// Convert `image` into a canvas.
+    ///
+    /// Transparent pixels are treated as dark, so they are unset (even when inverted).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the width or height of the canvas in pixels doesn't fit in a `u32`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use braillefb::image::Converter;
+    /// use image::{DynamicImage, GrayImage, Luma};
+    ///
+    /// // A white square with a black circle in the middle
+    /// let image = GrayImage::from_fn(64, 64, |x, y| {
+    ///     let (dx, dy) = (x as i32 - 32, y as i32 - 32);
+    ///     Luma([if dx * dx + dy * dy < 20 * 20 { 0 } else { 255 }])
+    /// });
+    ///
+    /// let canvas = Converter::new()
+    ///     .width(4)
+    ///     .apply(&DynamicImage::ImageLuma8(image));
+    /// assert_eq!((8, 8), (canvas.width(), canvas.height()));
+    /// assert_eq!("⣿⠛⠛⣿\n⣿⣤⣤⣿\n", &canvas.to_string());
+    /// ```
+    pub fn apply(&self, image: &DynamicImage) -> Canvas {
+        let (width, height) = self.size(image.width(), image.height());
+        if width == 0 || height == 0 {
+            return Canvas::new(width, height);
+        }
+
+        let size = |n: usize| u32::try_from(n).expect("canvas size does not fit in u32");
+        let resized = image.resize_exact(size(width), size(height), self.filter);
+        let luma = resized
+            .to_luma_alpha8()
+            .pixels()
+            .map(|pixel| {
+                let [luma, alpha] = pixel.0;
+                let luma = if self.invert { 255 - luma } else { luma };
+                (luma as u32 * alpha as u32 / 255) as u8
+            })
+            .collect::<Vec<_>>();
+
+        let mut canvas = Canvas::new(width, height);
+        let pixels = self.dither.apply(&luma, width, height);
+        for (i, _) in pixels.iter().enumerate().filter(|(_, &p)| p) {
+            canvas.set(i % width, i / width);
+        }
+        canvas
+    }
+
+    // The size of the canvas in pixels
+    fn size(&self, width: u32, height: u32) -> (usize, usize) {
+        if width == 0 || height == 0 {
+            return (0, 0);
+        }
+
+        // The width of the canvas divided by its height that shows the image undistorted
+        let (cell_width, cell_height) = self.glyph_set.cell_size();
+        let ratio = width as f64 / height as f64 * self.cell_aspect * cell_width as f64
+            / cell_height as f64;
+        let round = |n: f64| (n.round() as usize).max(1);
+
+        let pixels = |cells: usize, cell_size: usize| {
+            cells
+                .checked_mul(cell_size)
+                .expect("canvas size overflows usize")
+        };
+        match (
+            self.cols.map(|cols| pixels(cols, cell_width)),
+            self.rows.map(|rows| pixels(rows, cell_height)),
+        ) {
+            (Some(w), Some(h)) if !self.preserve_aspect => (w, h),
+            (Some(w), Some(h)) if w as f64 / ratio <= h as f64 => (w, round(w as f64 / ratio)),
+            (_, Some(h)) => (round(h as f64 * ratio), h),
+            (Some(w), None) => (w, round(w as f64 / ratio)),
+            (None, None) => (width as usize, round(width as f64 / ratio)),
+        }
+    }
+}
+
+impl Default for Converter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use image::imageops::FilterType;
+    use image::{DynamicImage, GrayImage, Luma, Rgba, RgbaImage};
+
+    use super::Converter;
+    use crate::GlyphSet;
+
+    // Light on the left half, dark on the right half
+    fn halves(width: u32, height: u32) -> DynamicImage {
+        DynamicImage::ImageLuma8(GrayImage::from_fn(width, height, |x, _| {
+            Luma([if x < width / 2 { 255 } else { 0 }])
+        }))
+    }
+
+    fn size(converter: Converter, image: &DynamicImage) -> (usize, usize) {
+        let canvas = converter.apply(image);
+        (canvas.width(), canvas.height())
+    }
+
+    #[test]
+    fn sizes() {
+        let image = halves(80, 40);
+        assert_eq!((80, 40), size(Converter::new(), &image));
+        assert_eq!((8, 4), size(Converter::new().width(4), &image));
+        assert_eq!((16, 8), size(Converter::new().height(2), &image));
+        assert_eq!((8, 4), size(Converter::new().width(4).height(4), &image));
+        assert_eq!((8, 4), size(Converter::new().width(8).height(1), &image));
+        assert_eq!(
+            (8, 8),
+            size(
+                Converter::new().width(4).height(2).preserve_aspect(false),
+                &image
+            )
+        );
+
+        // Quadrant pixels are twice as tall as they are wide
+        let quadrants = Converter::new().glyph_set(GlyphSet::Quadrant);
+        assert_eq!((8, 2), size(quadrants.width(4), &image));
+        assert_eq!((8, 4), size(quadrants.width(4).cell_aspect(1.0), &image));
+    }
+
+    #[test]
+    #[should_panic(expected = "cell aspect must be finite and positive")]
+    fn zero_cell_aspect() {
+        Converter::new().cell_aspect(0.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "canvas size does not fit in u32")]
+    fn oversized() {
+        Converter::new()
+            .width(u32::MAX as usize)
+            .height(1)
+            .preserve_aspect(false)
+            .apply(&halves(8, 8));
+    }
+
+    #[test]
+    fn pixels() {
+        let image = halves(16, 16);
+        let converter = Converter::new().width(2).filter(FilterType::Nearest);
+        assert_eq!("⣿⠀\n", &converter.apply(&image).to_string());
+        assert_eq!("⠀⣿\n", &converter.invert(true).apply(&image).to_string());
+
+        // Transparent pixels are unset
+        let image = RgbaImage::from_fn(4, 4, |x, _| {
+            Rgba([255, 255, 255, if x < 2 { 255 } else { 0 }])
+        });
+        let canvas = Converter::new().apply(&DynamicImage::ImageRgba8(image));
+        assert_eq!("⣿⠀\n", &canvas.to_string());
+    }
+}
@@ -58,9 +58,77 @@
 //!     ])
 //! );
 //! ```
+//!
+//! # Features
+//!
+//! - `std` (default): Implies `alloc`, adds `Framebuffer::write_to`, the shapes in `shape` and
+//!   differential terminal updates in `terminal`.
+//! - `alloc`: Everything that allocates, like `Canvas`, fonts and dithering. Without `alloc` (and
+//!   `std`) the crate is `no_std` and only renders existing pixels with [`Framebuffer`].
+//! - `rayon`: Render large framebuffers in parallel with `Framebuffer::par_render` and
+//!   `Framebuffer::par_write_to`.
+//! - `embedded-graphics`: Implements `DrawTarget` for `Canvas`, so any [`embedded-graphics`][eg]
+//!   primitive, font or image can be drawn and then printed.
+//! - `ratatui`: Render framebuffers in a [ratatui][rt] terminal UI with
+//!   `widget::FramebufferWidget`.
+//! - `animation`: A double-buffered animation loop with frame pacing in `animation`, which restores
+//!   the terminal when Ctrl-C is pressed.
+//! - `image`: Convert images loaded with the [`image`][im] crate (PNG, JPEG and GIF) in `image`.
+//!
+//! [eg]: https://docs.rs/embedded-graphics
+//! [rt]: https://ratatui.rs
+//! [im]: https://docs.rs/image
+
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+use core::fmt;
+use core::iter::FusedIterator;
+use core::marker::PhantomData;
+use core::ops::{Index, Range};
 
-use std::fmt;
-use std::ops::Index;
+#[cfg(feature = "animation")]
+pub mod animation;
+#[cfg(feature = "alloc")]
+mod canvas;
+#[cfg(feature = "alloc")]
+pub mod color;
+#[cfg(feature = "alloc")]
+pub mod dither;
+#[cfg(feature = "alloc")]
+pub mod draw;
+#[cfg(feature = "embedded-graphics")]
+mod embedded;
+mod error;
+#[cfg(feature = "alloc")]
+pub mod font;
+mod glyphs;
+#[cfg(feature = "image")]
+pub mod image;
+#[cfg(feature = "alloc")]
+pub mod netpbm;
+#[cfg(feature = "rayon")]
+mod parallel;
+#[cfg(feature = "alloc")]
+pub mod parse;
+mod render;
+#[cfg(feature = "std")]
+pub mod shape;
+pub mod source;
+#[cfg(feature = "std")]
+pub mod terminal;
+#[cfg(feature = "ratatui")]
+pub mod widget;
+
+#[cfg(feature = "alloc")]
+pub use canvas::Canvas;
+pub use error::Error;
+pub use glyphs::GlyphSet;
+pub use source::PixelSource;
+
+use source::{Cells, FromFn, Pixels};
 
 // https://en.wikipedia.org/wiki/Braille_Patterns
 //
@@ -81,6 +149,15 @@ const BIT_OFFSETS: [(usize, usize); 8] = [
 const CHAR_WIDTH: usize = 2;
 const CHAR_HEIGHT: usize = 4;
 
+// The bit for each dot of a packed cell, indexed by `[y][x]`. A packed cell is the offset of its
+// braille `char` from U+2800, so bit 0 is dot 1 and bit 7 is dot 8.
+const DOT_BITS: [[u8; CHAR_WIDTH]; CHAR_HEIGHT] = [
+    [0x01, 0x08], // 1 4
+    [0x02, 0x10], // 2 5
+    [0x04, 0x20], // 3 6
+    [0x40, 0x80], // 7 8
+];
+
 // Hardcode the list a `char`s so we can return static references from the `Index` impl
 const CHARS: [char; 256] = [
     '⠀', '⠁', '⠂', '⠃', '⠄', '⠅', '⠆', '⠇', '⠈', '⠉', '⠊', '⠋', '⠌', '⠍', '⠎', '⠏', '⠐', '⠑', '⠒',
@@ -124,13 +201,16 @@ const CHARS: [char; 256] = [
 /// let output: String = f.into_iter().collect();
 /// assert_eq!("⣇⠽\n", &output);
 /// ```
+///
+/// Any [`PixelSource`] can be rendered, not just `&[bool]` slices, see [`Framebuffer::from_source`].
+/// Block elements can be used instead of braille, see [`Framebuffer::glyph_set`].
 #[derive(Debug, Copy, Clone)]
-pub struct Framebuffer<'a> {
-    framebuffer: &'a [bool],
-    width: usize,
-    height: usize,
-    x_chars_count: usize,
-    y_chars_count: usize,
+pub struct Framebuffer<'a, S = Pixels<'a>> {
+    source: S,
+    glyph_set: GlyphSet,
+    line_ending: LineEnding,
+    trailing_line_ending: bool,
+    marker: PhantomData<&'a ()>,
 }
 
 impl<'a> Framebuffer<'a> {
@@ -138,30 +218,162 @@ impl<'a> Framebuffer<'a> {
     ///
     /// # Panics
     ///
-    /// Panics if length of supplied `framebuffer` slice is not equal to `width * height`.
+    /// Panics if length of supplied `framebuffer` slice is not equal to `width * height`, or if
+    /// `width * height` overflows. See [`Framebuffer::try_new`] for a non-panicking version.
     pub fn new(framebuffer: &'a [bool], width: usize, height: usize) -> Self {
-        assert_eq!(
-            framebuffer.len(),
-            width * height,
-            "supplied slice does not match width * height"
-        );
+        Self::from_source(Pixels::new(framebuffer, width, height))
+    }
 
-        fn round_up(input: usize, multiple: usize) -> usize {
-            ((input + multiple - 1) / multiple) * multiple
-        }
+    /// Create a Framebuffer instance, returning an error if the supplied `framebuffer` slice
+    /// doesn't match `width * height`.
+    ///
+    /// Unlike [`Framebuffer::new`], zero-sized dimensions are rejected.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use braillefb::{Error, Framebuffer};
+    /// let framebuffer = vec![false; 4 * 4];
+    ///
+    /// assert!(Framebuffer::try_new(&framebuffer, 4, 4).is_ok());
+    /// assert_eq!(
+    ///     Err(Error::LengthMismatch { expected: 8, actual: 16 }),
+    ///     Framebuffer::try_new(&framebuffer, 2, 4).map(|_| ())
+    /// );
+    /// assert_eq!(
+    ///     Err(Error::ZeroSized { width: 0, height: 4 }),
+    ///     Framebuffer::try_new(&[], 0, 4).map(|_| ())
+    /// );
+    /// ```
+    pub fn try_new(framebuffer: &'a [bool], width: usize, height: usize) -> Result<Self, Error> {
+        Pixels::try_new(framebuffer, width, height).map(Self::from_source)
+    }
+}
+
+impl<'a> Framebuffer<'a, Cells<'a>> {
+    /// Create a Framebuffer instance from bit-packed cells.
+    ///
+    /// Each `u8` holds the eight dots of one braille `char` (bit 0 is dot 1 through to bit 7 being
+    /// dot 8), so the `char` is simply U+2800 plus the value of the cell. Cells are stored in
+    /// row-major order with `width / 2` (rounded up) cells per row and `height / 4` (rounded up)
+    /// rows. Bits for dots outside of `width` and `height` should be unset.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of supplied `cells` slice does not match the number of cells, or if
+    /// the number of cells overflows.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use braillefb::Framebuffer;
+    /// let cells = [0b1100_0111, 0b0011_1101];
+    ///
+    /// let f = Framebuffer::from_cells(&cells, 4, 4);
+    /// assert_eq!("⣇⠽\n", &f.to_string());
+    /// ```
+    pub fn from_cells(cells: &'a [u8], width: usize, height: usize) -> Self {
+        Self::from_source(Cells::new(cells, width, height))
+    }
+
+    /// Create a Framebuffer instance from bit-packed cells, returning an error if the supplied
+    /// `cells` slice doesn't match the number of cells.
+    ///
+    /// See [`Framebuffer::from_cells`] for the layout of the cells.
+    pub fn try_from_cells(cells: &'a [u8], width: usize, height: usize) -> Result<Self, Error> {
+        Cells::try_new(cells, width, height).map(Self::from_source)
+    }
+}
 
-        let x_chars_count = (round_up(width, CHAR_WIDTH) / CHAR_WIDTH) + 1; // + 1 for linebreaks
-        let y_chars_count = round_up(height, CHAR_HEIGHT) / CHAR_HEIGHT;
+impl<F: Fn(usize, usize) -> bool> Framebuffer<'_, FromFn<F>> {
+    /// Create a Framebuffer instance that calls `f` with `x`, `y` for each pixel.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use braillefb::Framebuffer;
+    /// let f = Framebuffer::from_fn(4, 4, |x, _y| x % 2 == 0);
+    /// assert_eq!("⡇⡇\n", &f.to_string());
+    /// ```
+    pub fn from_fn(width: usize, height: usize, f: F) -> Self {
+        Self::from_source(FromFn::new(width, height, f))
+    }
+}
 
+impl<S: PixelSource> Framebuffer<'_, S> {
+    /// Create a Framebuffer instance from any [`PixelSource`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use braillefb::{source::Pixels, Framebuffer};
+    /// let pixels = [
+    ///     true, false,
+    ///     true, true,
+    ///     true, false,
+    ///     false, true,
+    /// ];
+    ///
+    /// let f = Framebuffer::from_source(Pixels::new(&pixels, 2, 4));
+    /// assert_eq!("⢗\n", &f.to_string());
+    /// ```
+    pub fn from_source(source: S) -> Self {
         Self {
-            framebuffer,
-            width,
-            height,
-            x_chars_count,
-            y_chars_count,
+            source,
+            glyph_set: GlyphSet::Braille,
+            line_ending: LineEnding::Lf,
+            trailing_line_ending: true,
+            marker: PhantomData,
         }
     }
 
+    /// Render with the `char`s of `glyph_set`, defaults to [`GlyphSet::Braille`].
+    ///
+    /// The glyph set decides how many pixels each `char` covers, so this changes
+    /// [`Framebuffer::x_chars_count`], [`Framebuffer::y_chars_count`] and [`Framebuffer::len`].
+    pub fn glyph_set(mut self, glyph_set: GlyphSet) -> Self {
+        self.glyph_set = glyph_set;
+        self
+    }
+
+    /// Separate rows with `line_ending`, defaults to [`LineEnding::Lf`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use braillefb::{Framebuffer, LineEnding};
+    /// let f = Framebuffer::from_fn(2, 8, |_, _| true);
+    ///
+    /// assert_eq!("⣿\r\n⣿\r\n", &f.line_ending(LineEnding::Crlf).to_string());
+    /// assert_eq!("⣿|⣿|", &f.line_ending(LineEnding::Custom(&['|'])).to_string());
+    /// assert_eq!("⣿⣿", &f.line_ending(LineEnding::None).to_string());
+    /// ```
+    pub fn line_ending(mut self, line_ending: LineEnding) -> Self {
+        self.line_ending = line_ending;
+        self
+    }
+
+    /// Whether the last row is followed by a line ending, defaults to `true`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use braillefb::Framebuffer;
+    /// let f = Framebuffer::from_fn(2, 8, |_, _| true).trailing_line_ending(false);
+    ///
+    /// assert_eq!("⣿\n⣿", &f.to_string());
+    /// assert_eq!(3, f.len());
+    /// ```
+    pub fn trailing_line_ending(mut self, trailing_line_ending: bool) -> Self {
+        self.trailing_line_ending = trailing_line_ending;
+        self
+    }
+
+    /// Returns a reference to the underlying [`PixelSource`].
+    pub fn source(&self) -> &S {
+        &self.source
+    }
+
     /// Get the nth braille character in the framebuffer.
     ///
     /// # Example
@@ -189,19 +401,80 @@ impl<'a> Framebuffer<'a> {
 
     fn get_inner(&self, index: usize) -> Option<&'static char> {
         match self.offsets(index) {
-            Offsets::Char(x_offset, y_offset) => Some(get_char(
-                self.framebuffer,
-                x_offset,
-                y_offset,
-                self.width,
-                self.height,
-            )),
-            Offsets::Linebreak => Some(&'\n'),
+            Offsets::Char(x_offset, y_offset) => Some(self.char_at(x_offset, y_offset)),
+            Offsets::Separator(i) => Some(&self.line_ending.chars()[i]),
             Offsets::End => None,
         }
     }
 
-    /// Returns the number of braille `chars` across the image including a trailing linebreak.
+    /// Get the `char` in column `col` and row `row`, without having to account for line endings.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use braillefb::Framebuffer;
+    /// let f = Framebuffer::from_fn(4, 8, |x, y| x == 0 && y >= 4);
+    ///
+    /// assert_eq!(Some('⠀'), f.cell(0, 0));
+    /// assert_eq!(Some('⡇'), f.cell(0, 1));
+    /// assert_eq!('⠀', f[(1, 1)]);
+    /// assert_eq!(None, f.cell(2, 0)); // The line ending isn't a cell
+    /// ```
+    pub fn cell(&self, col: usize, row: usize) -> Option<char> {
+        self.cell_inner(col, row).copied()
+    }
+
+    fn cell_inner(&self, col: usize, row: usize) -> Option<&'static char> {
+        let (width, height) = self.glyph_set.cell_size();
+        (col < self.cols() && row < self.y_chars_count())
+            .then(|| self.char_at(col * width, row * height))
+    }
+
+    /// Iterate over the rows of `char`s, without line endings.
+    ///
+    /// Each [`Row`] is an iterator over its `char`s and implements [`Display`](fmt::Display).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use braillefb::Framebuffer;
+    /// let f = Framebuffer::from_fn(4, 8, |x, y| x == 0 && y >= 4);
+    ///
+    /// let rows: Vec<String> = f.rows().map(|row| row.to_string()).collect();
+    /// assert_eq!(vec!["⠀⠀", "⡇⠀"], rows);
+    /// ```
+    pub fn rows(&self) -> Rows<'_, '_, S> {
+        Rows {
+            inner: self,
+            row: 0,
+        }
+    }
+
+    /// Iterate over the `char`s along with their column and row, without line endings.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use braillefb::Framebuffer;
+    /// let f = Framebuffer::from_fn(4, 8, |x, y| x == 0 && y >= 4);
+    ///
+    /// let set: Vec<_> = f.cells().filter(|&(_, _, c)| c != '⠀').collect();
+    /// assert_eq!(vec![(0, 1, '⡇')], set);
+    /// ```
+    pub fn cells(&self) -> IndexedCells<'_, '_, S> {
+        IndexedCells {
+            inner: self,
+            index: 0,
+        }
+    }
+
+    fn char_at(&self, x_offset: usize, y_offset: usize) -> &'static char {
+        let (width, height) = self.glyph_set.cell_size();
+        self.glyph_set
+            .char_at(&self.source, x_offset / width, y_offset / height)
+    }
+
+    /// Returns the number of braille `chars` across the image including the line ending.
     ///
     /// # Example
     ///
@@ -224,7 +497,7 @@ impl<'a> Framebuffer<'a> {
     /// assert_eq!(3, f.x_chars_count());
     /// ```
     pub fn x_chars_count(&self) -> usize {
-        self.x_chars_count
+        self.cols() + self.line_ending.chars().len()
     }
 
     /// Returns the number of braille `chars` down the image.
@@ -250,47 +523,72 @@ impl<'a> Framebuffer<'a> {
     /// assert_eq!(2, f.y_chars_count());
     /// ```
     pub fn y_chars_count(&self) -> usize {
-        self.y_chars_count
+        self.source.height().div_ceil(self.glyph_set.cell_size().1)
     }
 
-    /// The number of `char`s (including linebreaks) that can be returned.
+    /// The number of `char`s (including line endings) that can be returned.
     pub fn len(&self) -> usize {
-        self.y_chars_count * self.x_chars_count
+        let len = self.y_chars_count() * self.x_chars_count();
+        if self.trailing_line_ending || len == 0 {
+            len
+        } else {
+            len - self.line_ending.chars().len()
+        }
     }
 
     pub fn is_empty(&self) -> bool {
-        self.framebuffer.is_empty()
+        self.source.width() * self.source.height() == 0
     }
 
-    fn offsets(&self, index: usize) -> Offsets {
-        if index > 0 && (index + 1) % self.x_chars_count == 0 {
-            return Offsets::Linebreak;
-        }
-
-        let rows = index / self.x_chars_count;
-        let y_offset = rows * CHAR_HEIGHT;
+    // The number of `char`s across the image, excluding the line ending
+    fn cols(&self) -> usize {
+        self.source.width().div_ceil(self.glyph_set.cell_size().0)
+    }
 
-        if y_offset >= self.height {
+    fn offsets(&self, index: usize) -> Offsets {
+        if index >= self.len() {
             return Offsets::End;
         }
 
-        let cols = index % self.x_chars_count;
-        let x_offset = cols * CHAR_WIDTH;
+        let (width, height) = self.glyph_set.cell_size();
+        let x_chars_count = self.x_chars_count();
+
+        let rows = index / x_chars_count;
+        let cols = index % x_chars_count;
 
-        Offsets::Char(x_offset, y_offset)
+        match cols.checked_sub(self.cols()) {
+            Some(i) => Offsets::Separator(i),
+            None => Offsets::Char(cols * width, rows * height),
+        }
     }
 }
 
-impl fmt::Display for Framebuffer<'_> {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        for c in self {
-            write!(f, "{}", c)?;
+/// What separates the rows of a [`Framebuffer`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub enum LineEnding {
+    /// `\n`
+    #[default]
+    Lf,
+    /// `\r\n`
+    Crlf,
+    /// Any `char`s.
+    Custom(&'static [char]),
+    /// Nothing, rows are joined together into a single stream of `char`s.
+    None,
+}
+
+impl LineEnding {
+    fn chars(self) -> &'static [char] {
+        match self {
+            LineEnding::Lf => &['\n'],
+            LineEnding::Crlf => &['\r', '\n'],
+            LineEnding::Custom(chars) => chars,
+            LineEnding::None => &[],
         }
-        Ok(())
     }
 }
 
-impl Index<usize> for Framebuffer<'_> {
+impl<S: PixelSource> Index<usize> for Framebuffer<'_, S> {
     type Output = char;
 
     fn index(&self, index: usize) -> &Self::Output {
@@ -304,14 +602,42 @@ impl Index<usize> for Framebuffer<'_> {
     }
 }
 
-impl<'a, 'f> IntoIterator for &'a Framebuffer<'f> {
+impl<S: PixelSource> Index<(usize, usize)> for Framebuffer<'_, S> {
+    type Output = char;
+
+    fn index(&self, (col, row): (usize, usize)) -> &Self::Output {
+        self.cell_inner(col, row).unwrap_or_else(|| {
+            panic!(
+                "index out of bounds: the size is {}x{} but the index is ({}, {})",
+                self.cols(),
+                self.y_chars_count(),
+                col,
+                row
+            )
+        })
+    }
+}
+
+impl<'a, 'f, S: PixelSource> IntoIterator for &'a Framebuffer<'f, S> {
     type Item = char;
-    type IntoIter = Iter<'a, 'f>;
+    type IntoIter = Iter<'a, 'f, S>;
 
     fn into_iter(self) -> Self::IntoIter {
         Iter {
             inner: self,
-            index: 0,
+            indices: 0..self.len(),
+        }
+    }
+}
+
+impl<'f, S: PixelSource> IntoIterator for Framebuffer<'f, S> {
+    type Item = char;
+    type IntoIter = IntoIter<'f, S>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter {
+            indices: 0..self.len(),
+            inner: self,
         }
     }
 }
@@ -319,45 +645,210 @@ impl<'a, 'f> IntoIterator for &'a Framebuffer<'f> {
 #[derive(Debug, PartialEq)]
 enum Offsets {
     Char(usize, usize),
-    Linebreak,
+    // The index into the `char`s of the line ending
+    Separator(usize),
     End,
 }
 
 /// An iterator over braille `char`s.
-pub struct Iter<'a, 'i> {
-    inner: &'a Framebuffer<'i>,
-    index: usize,
+///
+/// Every `char` is computed independently, so [`Iterator::nth`] and iterating in reverse don't
+/// need to compute the skipped `char`s.
+pub struct Iter<'a, 'i, S = Pixels<'i>> {
+    inner: &'a Framebuffer<'i, S>,
+    indices: Range<usize>,
+}
+
+impl<S> Clone for Iter<'_, '_, S> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner,
+            indices: self.indices.clone(),
+        }
+    }
 }
 
-impl<'a, 'i> Iterator for Iter<'a, 'i> {
+impl<S: PixelSource> Iterator for Iter<'_, '_, S> {
     type Item = char;
 
     fn next(&mut self) -> Option<Self::Item> {
-        match self.inner.offsets(self.index) {
-            Offsets::Char(x_offset, y_offset) => {
-                self.index += 1;
-                Some(*get_char(
-                    self.inner.framebuffer,
-                    x_offset,
-                    y_offset,
-                    self.inner.width,
-                    self.inner.height,
-                ))
-            }
-            Offsets::Linebreak => {
-                self.index += 1;
-                Some('\n')
-            }
-            Offsets::End => None,
+        self.indices.next().map(|index| self.inner[index])
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.indices.size_hint()
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.indices.nth(n).map(|index| self.inner[index])
+    }
+}
+
+impl<S: PixelSource> DoubleEndedIterator for Iter<'_, '_, S> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.indices.next_back().map(|index| self.inner[index])
+    }
+
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        self.indices.nth_back(n).map(|index| self.inner[index])
+    }
+}
+
+impl<S: PixelSource> ExactSizeIterator for Iter<'_, '_, S> {}
+
+impl<S: PixelSource> FusedIterator for Iter<'_, '_, S> {}
+
+/// An owning iterator over braille `char`s, so that it can be returned from functions.
+///
+/// # Example
+///
+/// ```
+/// # use braillefb::Framebuffer;
+/// fn checkerboard() -> impl DoubleEndedIterator<Item = char> {
+///     Framebuffer::from_fn(4, 4, |x, y| (x + y) % 2 == 0).into_iter()
+/// }
+///
+/// assert_eq!("⢕⢕\n", checkerboard().collect::<String>());
+/// assert_eq!("\n⢕⢕", checkerboard().rev().collect::<String>());
+/// ```
+#[derive(Debug, Clone)]
+pub struct IntoIter<'i, S = Pixels<'i>> {
+    inner: Framebuffer<'i, S>,
+    indices: Range<usize>,
+}
+
+impl<S: PixelSource> Iterator for IntoIter<'_, S> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.indices.next().map(|index| self.inner[index])
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.indices.size_hint()
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.indices.nth(n).map(|index| self.inner[index])
+    }
+}
+
+impl<S: PixelSource> DoubleEndedIterator for IntoIter<'_, S> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.indices.next_back().map(|index| self.inner[index])
+    }
+
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        self.indices.nth_back(n).map(|index| self.inner[index])
+    }
+}
+
+impl<S: PixelSource> ExactSizeIterator for IntoIter<'_, S> {}
+
+impl<S: PixelSource> FusedIterator for IntoIter<'_, S> {}
+
+/// An iterator over the rows of a [`Framebuffer`], see [`Framebuffer::rows`].
+pub struct Rows<'a, 'i, S = Pixels<'i>> {
+    inner: &'a Framebuffer<'i, S>,
+    row: usize,
+}
+
+impl<'a, 'i, S: PixelSource> Iterator for Rows<'a, 'i, S> {
+    type Item = Row<'a, 'i, S>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.row >= self.inner.y_chars_count() {
+            return None;
         }
+
+        let row = Row {
+            inner: self.inner,
+            row: self.row,
+            col: 0,
+        };
+        self.row += 1;
+        Some(row)
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        let size = self.inner.len();
+        let size = self.inner.y_chars_count().saturating_sub(self.row);
         (size, Some(size))
     }
 }
 
+impl<S: PixelSource> ExactSizeIterator for Rows<'_, '_, S> {}
+
+/// An iterator over the `char`s of a single row, without the line ending.
+pub struct Row<'a, 'i, S = Pixels<'i>> {
+    inner: &'a Framebuffer<'i, S>,
+    row: usize,
+    col: usize,
+}
+
+impl<S: PixelSource> Row<'_, '_, S> {
+    /// The index of the row, starting at 0.
+    pub fn index(&self) -> usize {
+        self.row
+    }
+}
+
+impl<S: PixelSource> Iterator for Row<'_, '_, S> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let c = self.inner.cell(self.col, self.row)?;
+        self.col += 1;
+        Some(c)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let size = self.inner.cols().saturating_sub(self.col);
+        (size, Some(size))
+    }
+}
+
+impl<S: PixelSource> ExactSizeIterator for Row<'_, '_, S> {}
+
+/// Writes the remaining `char`s of the row.
+impl<S: PixelSource> fmt::Display for Row<'_, '_, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for col in self.col..self.inner.cols() {
+            write!(f, "{}", self.inner[(col, self.row)])?;
+        }
+        Ok(())
+    }
+}
+
+/// An iterator over the `char`s of a [`Framebuffer`] with their column and row, see
+/// [`Framebuffer::cells`].
+pub struct IndexedCells<'a, 'i, S = Pixels<'i>> {
+    inner: &'a Framebuffer<'i, S>,
+    index: usize,
+}
+
+impl<S: PixelSource> Iterator for IndexedCells<'_, '_, S> {
+    type Item = (usize, usize, char);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let cols = self.inner.cols();
+        if cols == 0 {
+            return None;
+        }
+
+        let (col, row) = (self.index % cols, self.index / cols);
+        let c = self.inner.cell(col, row)?;
+        self.index += 1;
+        Some((col, row, c))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let size = (self.inner.cols() * self.inner.y_chars_count()).saturating_sub(self.index);
+        (size, Some(size))
+    }
+}
+
+impl<S: PixelSource> ExactSizeIterator for IndexedCells<'_, '_, S> {}
+
 /// Converts a single 2x4 1-bit array into a braille `char`.
 ///
 /// # Example
@@ -377,16 +868,42 @@ pub fn to_char(f: [bool; 8]) -> char {
     *get_char(&f, 0, 0, CHAR_WIDTH, CHAR_HEIGHT)
 }
 
-// The x/y offsets are combined with the BIT_OFFSETS to create a u8 in the order that a
-// UTF-8 braille character is represented
-//
-// 1 4
-// 2 5
-// 3 6
-// 7 8
-//
-// 0b00000000
-//   12345678
+/// Converts a braille `char` back into a 2x4 1-bit array, the inverse of [`to_char`].
+///
+/// Returns `None` if `c` isn't a braille `char`.
+///
+/// # Example
+///
+/// ```
+/// assert_eq!(
+///     Some([
+///         true, false,
+///         true, true,
+///         true, false,
+///         false, true,
+///     ]),
+///     braillefb::from_char('⢗')
+/// );
+/// assert_eq!(None, braillefb::from_char('a'));
+/// ```
+pub fn from_char(c: char) -> Option<[bool; 8]> {
+    let cell = from_char_cell(c)?;
+
+    let mut f = [false; 8];
+    for (y, bits) in DOT_BITS.iter().enumerate() {
+        for (x, bit) in bits.iter().enumerate() {
+            f[x + y * CHAR_WIDTH] = cell & bit != 0;
+        }
+    }
+    Some(f)
+}
+
+// Returns the bit-packed cell for a braille `char`
+fn from_char_cell(c: char) -> Option<u8> {
+    let offset = (c as u32).checked_sub(CHARS[0] as u32)?;
+    u8::try_from(offset).ok()
+}
+
 fn get_char(
     framebuffer: &[bool],
     x_offset: usize,
@@ -394,22 +911,14 @@ fn get_char(
     width: usize,
     height: usize,
 ) -> &'static char {
-    let mut n: u8 = 0;
-    for (x, y) in BIT_OFFSETS {
-        n <<= 1;
-        let xx = x_offset + x;
-        let yy = y_offset + y;
-        if xx >= width || yy >= height {
-            continue;
-        }
-        n |= framebuffer[xx + yy * width] as u8;
-    }
-    &CHARS[n as usize]
+    let cell =
+        Pixels::new(framebuffer, width, height).cell(x_offset / CHAR_WIDTH, y_offset / CHAR_HEIGHT);
+    &CHARS[cell as usize]
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{get_char, to_char, Framebuffer, Offsets};
+    use super::{from_char, get_char, to_char, Error, Framebuffer, GlyphSet, LineEnding, Offsets};
 
     macro_rules! framebuffer {
         (#) => {true};
@@ -529,6 +1038,49 @@ mod tests {
         assert_eq!(Some('\n'), f.get(5));
     }
 
+    #[test]
+    fn try_new() {
+        let framebuffer = vec![false; 3 * 5];
+        assert!(Framebuffer::try_new(&framebuffer, 3, 5).is_ok());
+        assert_eq!(
+            Some(Error::LengthMismatch {
+                expected: 16,
+                actual: 15
+            }),
+            Framebuffer::try_new(&framebuffer, 4, 4).err()
+        );
+        assert_eq!(
+            Some(Error::ZeroSized {
+                width: 3,
+                height: 0
+            }),
+            Framebuffer::try_new(&[], 3, 0).err()
+        );
+        assert_eq!(
+            Some(Error::DimensionOverflow {
+                width: usize::MAX,
+                height: 2
+            }),
+            Framebuffer::try_new(&framebuffer, usize::MAX, 2).err()
+        );
+
+        let cells = vec![0; 4];
+        assert!(Framebuffer::try_from_cells(&cells, 3, 5).is_ok());
+        assert_eq!(
+            Some(Error::LengthMismatch {
+                expected: 2,
+                actual: 4
+            }),
+            Framebuffer::try_from_cells(&cells, 4, 4).err()
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "width * height overflows usize")]
+    fn new_overflow() {
+        Framebuffer::new(&[], usize::MAX, 2);
+    }
+
     #[test]
     fn test_to_char() {
         // ⢗
@@ -541,6 +1093,15 @@ mod tests {
         assert_eq!('⢗', to_char(framebuffer.try_into().unwrap()));
     }
 
+    #[test]
+    fn test_from_char() {
+        for c in super::CHARS {
+            assert_eq!(c, to_char(from_char(c).unwrap()));
+        }
+        assert_eq!(None, from_char('\u{27FF}'));
+        assert_eq!(None, from_char('\u{2900}'));
+    }
+
     #[test]
     fn test_get_char() {
         // ⠇⠅
@@ -559,6 +1120,108 @@ mod tests {
         assert_eq!(&'⠁', get_char(&framebuffer, 2, 4, 3, 5));
     }
 
+    #[test]
+    fn iterator_protocol() {
+        let f = Framebuffer::from_fn(6, 8, |x, y| (x * y) % 3 == 0);
+        let s = f.to_string();
+        let chars = s.chars().collect::<Vec<_>>();
+
+        let mut iter = (&f).into_iter();
+        assert_eq!(8, iter.len());
+        iter.next();
+        assert_eq!((7, Some(7)), iter.size_hint());
+        assert_eq!(Some(chars[7]), iter.next_back());
+        assert_eq!(Some(chars[2]), iter.nth(1));
+        assert_eq!(Some(chars[5]), iter.nth_back(1));
+        assert_eq!(2, iter.len());
+        assert_eq!(Some(chars[3]), iter.next());
+        assert_eq!(Some(chars[4]), iter.next_back());
+        assert_eq!(None, iter.next());
+        assert_eq!(None, iter.next_back());
+
+        let reversed = (&f).into_iter().rev().collect::<Vec<_>>();
+        assert_eq!(chars.iter().rev().copied().collect::<Vec<_>>(), reversed);
+        assert_eq!(None, (&f).into_iter().nth(8));
+
+        // Owning
+        let owned = Framebuffer::from_fn(6, 8, |x, y| (x * y) % 3 == 0);
+        assert_eq!(s, owned.into_iter().collect::<String>());
+    }
+
+    #[test]
+    fn cells_and_rows() {
+        let f = Framebuffer::from_fn(5, 5, |x, y| x == y).glyph_set(GlyphSet::Quadrant);
+        let rows = f
+            .rows()
+            .map(|row| row.collect::<String>())
+            .collect::<Vec<_>>();
+        assert_eq!(vec!["▚  ", " ▚ ", "  ▘"], rows);
+        assert_eq!(3, f.rows().len());
+
+        let cells = f.cells().collect::<Vec<_>>();
+        assert_eq!(9, cells.len());
+        assert_eq!((2, 0, ' '), cells[2]);
+        assert_eq!((0, 1, ' '), cells[3]);
+        for (col, row, c) in cells {
+            assert_eq!(Some(c), f.cell(col, row));
+            assert_eq!(c, f[(col, row)]);
+        }
+
+        // Rows match the rendered lines
+        let s = f.to_string();
+        for (row, line) in f.rows().zip(s.lines()) {
+            assert_eq!(line, row.to_string());
+        }
+
+        let mut row = f.rows().nth(1).unwrap();
+        assert_eq!(1, row.index());
+        assert_eq!(Some(' '), row.next());
+        assert_eq!("▚ ", row.to_string());
+
+        assert_eq!(None, f.cell(3, 0));
+        assert_eq!(None, f.cell(0, 3));
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bounds: the size is 3x3 but the index is (3, 0)")]
+    fn cell_out_of_bounds() {
+        let f = Framebuffer::from_fn(5, 5, |x, y| x == y).glyph_set(GlyphSet::Quadrant);
+        let _ = f[(3, 0)];
+    }
+
+    #[test]
+    fn line_endings() {
+        let framebuffer = vec![true; 4 * 8];
+        for line_ending in [
+            LineEnding::Lf,
+            LineEnding::Crlf,
+            LineEnding::Custom(&['<', '>', '|']),
+            LineEnding::None,
+        ] {
+            for trailing in [true, false] {
+                let f = Framebuffer::new(&framebuffer, 4, 8)
+                    .line_ending(line_ending)
+                    .trailing_line_ending(trailing);
+                let s = f.to_string();
+
+                assert_eq!(f.len(), s.chars().count());
+                assert_eq!(2, f.y_chars_count());
+                assert_eq!(2 + line_ending.chars().len(), f.x_chars_count());
+                for (i, c) in s.chars().enumerate() {
+                    assert_eq!(Some(c), f.get(i));
+                    assert_eq!(c, f[i]);
+                }
+                assert_eq!(None, f.get(f.len()));
+                assert_eq!(None, f.get(f.len() + f.x_chars_count()));
+            }
+        }
+
+        let f = Framebuffer::new(&framebuffer, 4, 8).line_ending(LineEnding::Crlf);
+        assert_eq!("⣿⣿\r\n⣿⣿\r\n", &f.to_string());
+        let f = f.trailing_line_ending(false);
+        assert_eq!("⣿⣿\r\n⣿⣿", &f.to_string());
+    }
+
     #[test]
     fn offsets() {
         // 0 # 1 #
@@ -580,17 +1243,17 @@ mod tests {
             // Row 1
             assert_eq!(Offsets::Char(0, 0), f.offsets(0));
             assert_eq!(Offsets::Char(2, 0), f.offsets(1));
-            assert_eq!(Offsets::Linebreak, f.offsets(2));
+            assert_eq!(Offsets::Separator(0), f.offsets(2));
 
             // Row 2
             assert_eq!(Offsets::Char(0, 4), f.offsets(3));
             assert_eq!(Offsets::Char(2, 4), f.offsets(4));
-            assert_eq!(Offsets::Linebreak, f.offsets(5));
+            assert_eq!(Offsets::Separator(0), f.offsets(5));
 
             // Row 3
             assert_eq!(Offsets::Char(0, 8), f.offsets(6));
             assert_eq!(Offsets::Char(2, 8), f.offsets(7));
-            assert_eq!(Offsets::Linebreak, f.offsets(8));
+            assert_eq!(Offsets::Separator(0), f.offsets(8));
 
             assert_eq!(Offsets::End, f.offsets(9));
         }
@@ -605,6 +1268,37 @@ mod tests {
         test(f);
     }
 
+    #[test]
+    fn cells() {
+        // ⠇⠅
+        // ⠉⠁
+        let framebuffer = framebuffer![
+            # . #
+            # . .
+            # . #
+            . . .
+            # # #
+        ];
+        let pixels = Framebuffer::new(&framebuffer, 3, 5);
+
+        let cells = [0b0000_0111, 0b0000_0101, 0b0000_1001, 0b0000_0001];
+        let cells = Framebuffer::from_cells(&cells, 3, 5);
+
+        assert_eq!(pixels.to_string(), cells.to_string());
+        assert_eq!(pixels.len(), cells.len());
+    }
+
+    #[test]
+    fn dot_bits() {
+        for (y, row) in super::DOT_BITS.iter().enumerate() {
+            for (x, bit) in row.iter().enumerate() {
+                let mut f = [false; 8];
+                f[x + y * 2] = true;
+                assert_eq!(super::CHARS[*bit as usize], to_char(f));
+            }
+        }
+    }
+
     #[test]
     fn chars() {
         let chars = (0..256)
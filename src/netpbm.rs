@@ -0,0 +1,485 @@
+//! Reading and writing [Netpbm][1] images (PBM, PGM and PPM).
+//!
+//! [`read`] accepts all six formats, both plain (ASCII) and raw (binary). Bitmaps can be rendered
+//! as they are, graymaps and pixmaps are converted to dots with a [`Dither`]. [`encode`] writes any
+//! [`PixelSource`] as a PBM bitmap.
+//!
+//! In PBM files 1 is black, and set pixels are written as 1s so that images round-trip.
+//!
+//! # Example
+//!
+//! ```
+//! # use braillefb::{dither::{Dither, Method}, netpbm::{self, Encoding}};
+//! # use braillefb::{source::Pixels, Framebuffer};
+//! let image = netpbm::read(b"P2\n4 4\n9\n0 9 0 9\n0 9 0 9\n9 0 9 0\n9 0 9 0\n").unwrap();
+//! let pixels = image.to_pixels(Dither::new(Method::Otsu));
+//!
+//! let f = Framebuffer::new(&pixels, 4, 4);
+//! assert_eq!("⡜⡜\n", &f.to_string());
+//!
+//! let pbm = netpbm::encode(Pixels::new(&pixels, 4, 4), Encoding::Plain);
+//! assert_eq!(b"P1\n4 4\n0101\n0101\n1010\n1010\n", &pbm[..]);
+//! ```
+//!
+//! [1]: https://netpbm.sourceforge.net/doc/
+
+use alloc::format;
+use alloc::vec::Vec;
+use core::fmt;
+#[cfg(feature = "std")]
+use std::io;
+
+use crate::color::luma;
+use crate::dither::Dither;
+use crate::PixelSource;
+
+// The longest line allowed in plain files
+const MAX_LINE_LENGTH: usize = 70;
+
+/// The error type returned when a Netpbm image can't be read.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum NetpbmError {
+    /// The data doesn't start with one of `P1` to `P6`.
+    InvalidMagic,
+    /// The header ended before the named field (`"width"`, `"height"` or `"maxval"`).
+    MissingField(&'static str),
+    /// The named header field isn't a number, is out of range or isn't followed by whitespace.
+    InvalidField(&'static str),
+    /// A sample in a plain image isn't a number, or is larger than the maxval.
+    InvalidSample,
+    /// The data ended before all of the samples.
+    UnexpectedEof,
+}
+
+impl fmt::Display for NetpbmError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NetpbmError::InvalidMagic => write!(f, "not a Netpbm image"),
+            NetpbmError::MissingField(field) => write!(f, "Netpbm header is missing the {}", field),
+            NetpbmError::InvalidField(field) => write!(f, "invalid {} in Netpbm header", field),
+            NetpbmError::InvalidSample => write!(f, "invalid sample in Netpbm image"),
+            NetpbmError::UnexpectedEof => write!(f, "unexpected end of Netpbm image data"),
+        }
+    }
+}
+
+impl core::error::Error for NetpbmError {}
+
+/// An image read from a Netpbm file.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    data: Data,
+}
+
+/// The pixels of an [`Image`], in row-major order.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Data {
+    /// The pixels of a PBM bitmap (`P1` or `P4`), set where the file has a 1.
+    Bits(Vec<bool>),
+    /// The luminance of a PGM graymap (`P2` or `P5`), scaled to `0..=255`.
+    Gray(Vec<u8>),
+    /// The RGB colors of a PPM pixmap (`P3` or `P6`), scaled to `0..=255`.
+    Color(Vec<[u8; 3]>),
+}
+
+/// Whether a PBM file is written as ASCII or binary.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub enum Encoding {
+    /// ASCII `0`s and `1`s (`P1`).
+    Plain,
+    /// Eight pixels per byte (`P4`).
+    #[default]
+    Raw,
+}
+
+impl Image {
+    /// The width of the image in pixels.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// The height of the image in pixels.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The pixels of the image.
+    pub fn data(&self) -> &Data {
+        &self.data
+    }
+
+    /// Take the pixels of the image.
+    pub fn into_data(self) -> Data {
+        self.data
+    }
+
+    /// The luminance of each pixel. Set pixels of a bitmap are 255 and unset pixels 0, so that
+    /// thresholding them gives back the same pixels.
+    pub fn luma(&self) -> Vec<u8> {
+        match &self.data {
+            Data::Bits(pixels) => pixels.iter().map(|&p| if p { 255 } else { 0 }).collect(),
+            Data::Gray(luma) => luma.clone(),
+            Data::Color(colors) => luma(colors),
+        }
+    }
+
+    /// Convert the image into pixels that can be rendered with
+    /// [`Framebuffer::new`](crate::Framebuffer::new).
+    ///
+    /// Bitmaps are returned as they are, graymaps and pixmaps are converted with `dither`.
+    pub fn to_pixels(&self, dither: Dither) -> Vec<bool> {
+        match &self.data {
+            Data::Bits(pixels) => pixels.clone(),
+            _ => dither.apply(&self.luma(), self.width, self.height),
+        }
+    }
+}
+
+/// Read a Netpbm image in any of the `P1` to `P6` formats.
+///
+/// Samples with a maxval other than 255 (including 16-bit samples) are scaled to `0..=255`. Only
+/// the first image of a file with several images is read.
+///
+/// # Example
+///
+/// ```
+/// # use braillefb::netpbm::{self, Data, NetpbmError};
+/// let image = netpbm::read(b"P4\n# A comment\n3 2\n\xa0\x40").unwrap();
+/// assert_eq!((3, 2), (image.width(), image.height()));
+/// assert_eq!(
+///     &Data::Bits(vec![true, false, true, false, true, false]),
+///     image.data()
+/// );
+///
+/// assert_eq!(
+///     Err(NetpbmError::InvalidField("maxval")),
+///     netpbm::read(b"P5 1 1 0 \x00")
+/// );
+/// ```
+pub fn read(data: &[u8]) -> Result<Image, NetpbmError> {
+    let format = match data {
+        [b'P', n @ b'1'..=b'6', ..] => n - b'0',
+        _ => return Err(NetpbmError::InvalidMagic),
+    };
+    let mut reader = Reader { data, pos: 2 };
+
+    let width = reader.field("width")?;
+    let height = reader.field("height")?;
+    let maxval = match format {
+        1 | 4 => 1,
+        _ => reader.field("maxval")?,
+    };
+    if !(1..=u16::MAX as usize).contains(&maxval) {
+        return Err(NetpbmError::InvalidField("maxval"));
+    }
+    let channels = if format == 3 || format == 6 { 3 } else { 1 };
+    let count = width
+        .checked_mul(height)
+        .and_then(|count| count.checked_mul(channels))
+        .ok_or(NetpbmError::InvalidField("height"))?;
+
+    let data = match format {
+        1 => Data::Bits(reader.plain_bits(count)?),
+        4 => Data::Bits(reader.raw_bits(width, height)?),
+        _ => {
+            let samples = if format <= 3 {
+                reader.plain_samples(count, maxval)?
+            } else {
+                reader.raw_samples(count, maxval)?
+            };
+            let samples = samples
+                .into_iter()
+                .map(|sample| ((sample * 255 + maxval / 2) / maxval) as u8);
+            if channels == 1 {
+                Data::Gray(samples.collect())
+            } else {
+                let samples = samples.collect::<Vec<_>>();
+                Data::Color(
+                    samples
+                        .chunks_exact(3)
+                        .map(|rgb| [rgb[0], rgb[1], rgb[2]])
+                        .collect(),
+                )
+            }
+        }
+    };
+
+    Ok(Image {
+        width,
+        height,
+        data,
+    })
+}
+
+/// Encode the pixels of `source` as a PBM bitmap, with set pixels as 1s.
+///
+/// Plain bitmaps have a line for every row, which is wrapped to stay within 70 characters.
+///
+/// # Example
+///
+/// ```
+/// # use braillefb::{netpbm::{self, Encoding}, Canvas};
+/// let mut canvas = Canvas::new(10, 1);
+/// canvas.set(0, 0);
+/// canvas.set(9, 0);
+/// assert_eq!(b"P4\n10 1\n\x80\x40", &netpbm::encode(&canvas, Encoding::Raw)[..]);
+/// ```
+pub fn encode<S: PixelSource>(source: S, encoding: Encoding) -> Vec<u8> {
+    let (width, height) = (source.width(), source.height());
+    let magic = match encoding {
+        Encoding::Plain => "P1",
+        Encoding::Raw => "P4",
+    };
+
+    let mut out = format!("{}\n{} {}\n", magic, width, height).into_bytes();
+
+    for y in 0..height {
+        match encoding {
+            Encoding::Plain => {
+                for x in 0..width {
+                    if x > 0 && x % MAX_LINE_LENGTH == 0 {
+                        out.push(b'\n');
+                    }
+                    out.push(if source.pixel(x, y) { b'1' } else { b'0' });
+                }
+                out.push(b'\n');
+            }
+            Encoding::Raw => {
+                for byte_x in (0..width).step_by(8) {
+                    let byte = (byte_x..width.min(byte_x + 8))
+                        .filter(|&x| source.pixel(x, y))
+                        .fold(0, |byte, x| byte | 0x80 >> (x - byte_x));
+                    out.push(byte);
+                }
+            }
+        }
+    }
+    out
+}
+
+/// Write the pixels of `source` to `out` as a PBM bitmap, see [`encode`].
+#[cfg(feature = "std")]
+pub fn write<S, W>(source: S, encoding: Encoding, out: &mut W) -> io::Result<()>
+where
+    S: PixelSource,
+    W: io::Write + ?Sized,
+{
+    out.write_all(&encode(source, encoding))
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl Reader<'_> {
+    // Skip whitespace and comments, which run until the end of the line
+    fn skip_space(&mut self) {
+        while let Some(&b) = self.data.get(self.pos) {
+            if b == b'#' {
+                while self
+                    .data
+                    .get(self.pos)
+                    .is_some_and(|&b| b != b'\n' && b != b'\r')
+                {
+                    self.pos += 1;
+                }
+            } else if b.is_ascii_whitespace() {
+                self.pos += 1;
+            } else {
+                break;
+            }
+        }
+    }
+
+    // A decimal number, `None` if there are no digits
+    fn number(&mut self) -> Option<Option<usize>> {
+        self.skip_space();
+        let start = self.pos;
+        while self.data.get(self.pos).is_some_and(u8::is_ascii_digit) {
+            self.pos += 1;
+        }
+        let digits = &self.data[start..self.pos];
+        (!digits.is_empty()).then(|| {
+            digits.iter().try_fold(0usize, |n, &d| {
+                n.checked_mul(10)?.checked_add((d - b'0') as usize)
+            })
+        })
+    }
+
+    fn field(&mut self, name: &'static str) -> Result<usize, NetpbmError> {
+        let value = match self.number() {
+            Some(value) => value.ok_or(NetpbmError::InvalidField(name))?,
+            None if self.pos == self.data.len() => return Err(NetpbmError::MissingField(name)),
+            None => return Err(NetpbmError::InvalidField(name)),
+        };
+        // The last field is followed by a single whitespace character before the raster
+        match self.data.get(self.pos) {
+            Some(b) if b.is_ascii_whitespace() => {}
+            Some(_) => return Err(NetpbmError::InvalidField(name)),
+            None => {}
+        }
+        Ok(value)
+    }
+
+    fn raster(&mut self, len: usize) -> Result<&[u8], NetpbmError> {
+        // Skip the whitespace after the header
+        let start = self.pos + 1;
+        let raster = start
+            .checked_add(len)
+            .and_then(|end| self.data.get(start..end))
+            .ok_or(NetpbmError::UnexpectedEof)?;
+        self.pos = start + len;
+        Ok(raster)
+    }
+
+    fn plain_bits(&mut self, count: usize) -> Result<Vec<bool>, NetpbmError> {
+        (0..count)
+            .map(|_| {
+                self.skip_space();
+                let bit = match self.data.get(self.pos) {
+                    Some(b'0') => false,
+                    Some(b'1') => true,
+                    Some(_) => return Err(NetpbmError::InvalidSample),
+                    None => return Err(NetpbmError::UnexpectedEof),
+                };
+                self.pos += 1;
+                Ok(bit)
+            })
+            .collect()
+    }
+
+    fn raw_bits(&mut self, width: usize, height: usize) -> Result<Vec<bool>, NetpbmError> {
+        let row_size = width.div_ceil(8);
+        let len = row_size
+            .checked_mul(height)
+            .ok_or(NetpbmError::InvalidField("height"))?;
+        let raster = self.raster(len)?;
+        Ok((0..width * height)
+            .map(|i| {
+                let (x, y) = (i % width, i / width);
+                raster[y * row_size + x / 8] & (0x80 >> (x % 8)) != 0
+            })
+            .collect())
+    }
+
+    fn plain_samples(&mut self, count: usize, maxval: usize) -> Result<Vec<usize>, NetpbmError> {
+        (0..count)
+            .map(|_| match self.number() {
+                Some(Some(sample)) if sample <= maxval => Ok(sample),
+                None if self.pos == self.data.len() => Err(NetpbmError::UnexpectedEof),
+                _ => Err(NetpbmError::InvalidSample),
+            })
+            .collect()
+    }
+
+    fn raw_samples(&mut self, count: usize, maxval: usize) -> Result<Vec<usize>, NetpbmError> {
+        // Samples are big-endian and 2 bytes wide when the maxval doesn't fit in a byte
+        let size = if maxval > 255 { 2 } else { 1 };
+        let len = count
+            .checked_mul(size)
+            .ok_or(NetpbmError::InvalidField("height"))?;
+        let raster = self.raster(len)?;
+        raster
+            .chunks_exact(size)
+            .map(
+                |sample| match sample.iter().fold(0, |n, &b| n << 8 | b as usize) {
+                    sample if sample <= maxval => Ok(sample),
+                    _ => Err(NetpbmError::InvalidSample),
+                },
+            )
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{encode, read, Data, Encoding, NetpbmError};
+    use crate::source::Pixels;
+
+    #[test]
+    fn bitmaps() {
+        let pixels = [
+            true, false, true, false, true, false, true, false, true, //
+            false, true, false, true, false, true, false, true, false,
+        ];
+        let source = Pixels::new(&pixels, 9, 2);
+        for encoding in [Encoding::Plain, Encoding::Raw] {
+            let image = read(&encode(source, encoding)).unwrap();
+            assert_eq!((9, 2), (image.width(), image.height()));
+            assert_eq!(&Data::Bits(pixels.to_vec()), image.data());
+        }
+
+        let image = read(b"P1 # comment\n2 2\n10\n01").unwrap();
+        assert_eq!(
+            Data::Bits(vec![true, false, false, true]),
+            image.into_data()
+        );
+    }
+
+    #[test]
+    fn plain_lines_are_wrapped() {
+        let pixels = [true; 75];
+        let pbm = encode(Pixels::new(&pixels, 75, 1), Encoding::Plain);
+        let lines = pbm
+            .split(|&b| b == b'\n')
+            .map(<[u8]>::len)
+            .collect::<Vec<_>>();
+        assert_eq!(vec![2, 4, 70, 5, 0], lines);
+    }
+
+    #[test]
+    fn graymaps_and_pixmaps() {
+        let image = read(b"P2 3 1 4 0 2 4").unwrap();
+        assert_eq!(&Data::Gray(vec![0, 128, 255]), image.data());
+        assert_eq!(read(b"P5 3 1 4\n\x00\x02\x04").unwrap(), image);
+
+        // 16-bit samples
+        let image = read(b"P5 2 1 65535\n\xff\xff\x80\x00").unwrap();
+        assert_eq!(&Data::Gray(vec![255, 128]), image.data());
+
+        let image = read(b"P3 2 1 255 255 0 0 0 0 255").unwrap();
+        assert_eq!(&Data::Color(vec![[255, 0, 0], [0, 0, 255]]), image.data());
+        assert_eq!(vec![76, 29], image.luma());
+        assert_eq!(
+            read(b"P6 2 1 255\n\xff\x00\x00\x00\x00\xff").unwrap(),
+            image
+        );
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(Err(NetpbmError::InvalidMagic), read(b"P7 1 1"));
+        assert_eq!(Err(NetpbmError::InvalidMagic), read(b""));
+        assert_eq!(Err(NetpbmError::MissingField("height")), read(b"P1 1 "));
+        assert_eq!(Err(NetpbmError::MissingField("maxval")), read(b"P2 1 1"));
+        assert_eq!(Err(NetpbmError::InvalidField("width")), read(b"P1 x 1"));
+        assert_eq!(Err(NetpbmError::InvalidField("width")), read(b"P1 -1 1"));
+        assert_eq!(
+            Err(NetpbmError::InvalidField("maxval")),
+            read(b"P2 1 1 65536 0")
+        );
+        assert_eq!(
+            Err(NetpbmError::InvalidField("width")),
+            read(b"P1 99999999999999999999999 1")
+        );
+        assert_eq!(
+            Err(NetpbmError::InvalidField("height")),
+            read(b"P4 9999999999 9999999999 ")
+        );
+        assert_eq!(Err(NetpbmError::InvalidSample), read(b"P1 2 1 0 2"));
+        assert_eq!(Err(NetpbmError::InvalidSample), read(b"P2 2 1 3 0 4"));
+        assert_eq!(Err(NetpbmError::InvalidSample), read(b"P5 1 1 3 \x04"));
+        assert_eq!(Err(NetpbmError::UnexpectedEof), read(b"P1 2 2 0 1 1"));
+        assert_eq!(
+            Err(NetpbmError::UnexpectedEof),
+            read(b"P4 9 2\n\x00\x00\x00")
+        );
+        assert_eq!(
+            Err(NetpbmError::UnexpectedEof),
+            read(b"P6 1 1 255\n\x00\x00")
+        );
+    }
+}
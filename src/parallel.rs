@@ -0,0 +1,83 @@
+use std::io;
+
+use rayon::prelude::*;
+
+use crate::{Framebuffer, PixelSource};
+
+// The number of rows `par_write_to` renders before writing them
+const BATCH_ROWS: usize = 256;
+
+impl<S: PixelSource + Sync> Framebuffer<'_, S> {
+    /// Render the `char`s into a `String`, rendering rows in parallel.
+    ///
+    /// The output is identical to [`Display`](std::fmt::Display), this is only faster for large
+    /// framebuffers.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use braillefb::Framebuffer;
+    /// let f = Framebuffer::from_fn(400, 400, |x, y| (x * y) % 7 == 0);
+    /// assert_eq!(f.to_string(), f.par_render());
+    /// ```
+    pub fn par_render(&self) -> String {
+        let mut out = String::new();
+        self.par_render_into(&mut out);
+        out
+    }
+
+    /// Append the `char`s to `out`, rendering rows in parallel.
+    pub fn par_render_into(&self, out: &mut String) {
+        let rows = (0..self.y_chars_count())
+            .into_par_iter()
+            .map(|row| self.row_chars(row).collect::<String>())
+            .collect::<Vec<_>>();
+
+        out.reserve(rows.iter().map(String::len).sum());
+        for row in rows {
+            out.push_str(&row);
+        }
+    }
+
+    /// Write the `char`s to `writer` as UTF-8, rendering rows in parallel.
+    ///
+    /// Rows are rendered in batches, so the whole output is never held in memory.
+    pub fn par_write_to<W: io::Write + ?Sized>(&self, writer: &mut W) -> io::Result<()> {
+        let rows = self.y_chars_count();
+        for start in (0..rows).step_by(BATCH_ROWS) {
+            let batch = (start..rows.min(start + BATCH_ROWS))
+                .into_par_iter()
+                .map(|row| self.row_chars(row).collect::<String>())
+                .collect::<Vec<_>>();
+            for row in batch {
+                writer.write_all(row.as_bytes())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Framebuffer, GlyphSet, LineEnding};
+
+    #[test]
+    fn matches_display() {
+        let f = Framebuffer::from_fn(997, 1001, |x, y| (x * 31 + y * 17) % 11 < 4);
+        for f in [
+            f,
+            f.glyph_set(GlyphSet::Sextant),
+            f.line_ending(LineEnding::Crlf).trailing_line_ending(false),
+        ] {
+            let expected = f.to_string();
+            assert_eq!(expected, f.par_render());
+
+            let mut out = Vec::new();
+            f.par_write_to(&mut out).unwrap();
+            assert_eq!(expected.as_bytes(), out);
+        }
+
+        let empty = Framebuffer::from_fn(0, 0, |_, _| true);
+        assert_eq!("", empty.par_render());
+    }
+}
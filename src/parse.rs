@@ -0,0 +1,130 @@
+//! Parsing braille text back into pixels.
+//!
+//! This is the inverse of the [`Display`](core::fmt::Display) impl of
+//! [`Framebuffer`](crate::Framebuffer), which makes it possible to store rendered images as text
+//! (in test fixtures for example) and edit them programmatically.
+//!
+//! # Example
+//!
+//! ```
+//! let mut canvas = braillefb::parse::parse("⣇⠽\n⡛⡼\n").unwrap();
+//! assert_eq!((4, 8), (canvas.width(), canvas.height()));
+//!
+//! canvas.toggle(1, 0);
+//! assert_eq!("⣏⠽\n⡛⡼\n", &canvas.to_string());
+//! ```
+
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::{from_char_cell, Canvas, CHAR_HEIGHT, CHAR_WIDTH};
+
+/// The error type returned when braille text can't be parsed.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// The line of the invalid `char`, starting at 1.
+    pub line: usize,
+    /// The column of the invalid `char` (counted in `char`s), starting at 1.
+    pub column: usize,
+    /// The invalid `char`.
+    pub found: char,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "expected a braille char but found {:?} at line {} column {}",
+            self.found, self.line, self.column
+        )
+    }
+}
+
+impl core::error::Error for ParseError {}
+
+/// Parses lines of braille `char`s into a [`Canvas`].
+///
+/// Every braille `char` becomes 2x4 pixels, so the canvas is twice as wide as the longest line and
+/// four times as tall as the number of lines. Shorter lines are padded with unset pixels. Both
+/// `\n` and `\r\n` line endings are accepted, and a trailing line ending is optional.
+///
+/// # Example
+///
+/// ```
+/// # use braillefb::parse::{parse, ParseError};
+/// assert_eq!(
+///     Err(ParseError { line: 2, column: 2, found: 'x' }),
+///     parse("⣇⠽\n⡛x\n")
+/// );
+/// ```
+pub fn parse(s: &str) -> Result<Canvas, ParseError> {
+    let mut rows = Vec::new();
+    for (line, text) in s.lines().enumerate() {
+        let row = text
+            .chars()
+            .enumerate()
+            .map(|(column, c)| {
+                from_char_cell(c).ok_or(ParseError {
+                    line: line + 1,
+                    column: column + 1,
+                    found: c,
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        rows.push(row);
+    }
+
+    let cols = rows.iter().map(Vec::len).max().unwrap_or(0);
+    let mut cells = Vec::with_capacity(cols * rows.len());
+    for row in &rows {
+        cells.extend_from_slice(row);
+        cells.resize(cells.len() + cols - row.len(), 0);
+    }
+
+    Ok(Canvas::from_cells(
+        cells,
+        cols * CHAR_WIDTH,
+        rows.len() * CHAR_HEIGHT,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse, ParseError};
+
+    #[test]
+    fn round_trip() {
+        let s = "⣇⠽⠀\n⡛⡼⣿\n";
+        assert_eq!(s, parse(s).unwrap().to_string());
+    }
+
+    #[test]
+    fn ragged_lines() {
+        let canvas = parse("⣿\r\n⣿⣿").unwrap();
+        assert_eq!((4, 8), (canvas.width(), canvas.height()));
+        assert_eq!("⣿⠀\n⣿⣿\n", &canvas.to_string());
+    }
+
+    #[test]
+    fn empty() {
+        let canvas = parse("").unwrap();
+        assert_eq!((0, 0), (canvas.width(), canvas.height()));
+    }
+
+    #[test]
+    fn invalid_char() {
+        let err = parse("⣿⣿\n⣿ ⣿").unwrap_err();
+        assert_eq!(
+            ParseError {
+                line: 2,
+                column: 2,
+                found: ' '
+            },
+            err
+        );
+        assert_eq!(
+            "expected a braille char but found ' ' at line 2 column 2",
+            err.to_string()
+        );
+    }
+}
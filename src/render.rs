@@ -0,0 +1,143 @@
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+#[cfg(feature = "alloc")]
+use core::convert::Infallible;
+use core::fmt;
+#[cfg(feature = "std")]
+use std::io;
+
+use crate::{Framebuffer, PixelSource};
+
+// The size of the stack buffer `char`s are encoded into before being written
+const CHUNK_SIZE: usize = 1024;
+
+impl<S: PixelSource> Framebuffer<'_, S> {
+    #[cfg(feature = "std")]
+    /// Write the `char`s to `writer` as UTF-8.
+    ///
+    /// The `char`s are encoded into a buffer on the stack and written in chunks, so nothing is
+    /// allocated and `writer` doesn't need to be buffered.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use braillefb::Framebuffer;
+    /// let f = Framebuffer::from_fn(4, 4, |x, _| x < 2);
+    ///
+    /// let mut out = Vec::new();
+    /// f.write_to(&mut out).unwrap();
+    /// assert_eq!("⣿⠀\n".as_bytes(), out);
+    /// ```
+    pub fn write_to<W: io::Write + ?Sized>(&self, writer: &mut W) -> io::Result<()> {
+        self.write_chunks(|chunk| writer.write_all(chunk.as_bytes()))
+    }
+
+    #[cfg(feature = "alloc")]
+    /// Append the `char`s to `out`.
+    ///
+    /// Reusing the same `String` for every frame of an animation avoids allocating, as long as
+    /// it's cleared first.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use braillefb::Framebuffer;
+    /// let mut out = String::new();
+    /// for frame in 0..2 {
+    ///     let f = Framebuffer::from_fn(4, 4, |x, _| x == frame);
+    ///
+    ///     out.clear();
+    ///     f.render_into(&mut out);
+    /// }
+    /// assert_eq!("⢸⠀\n", &out);
+    /// ```
+    pub fn render_into(&self, out: &mut String) {
+        // Braille `char`s are 3 bytes
+        out.reserve(self.len() * crate::CHARS[0].len_utf8());
+        let result = self.write_chunks(|chunk| {
+            out.push_str(chunk);
+            Ok::<_, Infallible>(())
+        });
+        match result {
+            Ok(()) => {}
+            Err(never) => match never {},
+        }
+    }
+
+    // Encode the `char`s into a buffer, calling `write` with each full buffer
+    fn write_chunks<E>(&self, mut write: impl FnMut(&str) -> Result<(), E>) -> Result<(), E> {
+        let mut buffer = [0; CHUNK_SIZE];
+        let mut len = 0;
+
+        for row in 0..self.y_chars_count() {
+            for c in self.row_chars(row) {
+                if len + c.len_utf8() > buffer.len() {
+                    write(chunk(&buffer[..len]))?;
+                    len = 0;
+                }
+                len += c.encode_utf8(&mut buffer[len..]).len();
+            }
+        }
+
+        if len > 0 {
+            write(chunk(&buffer[..len]))?;
+        }
+        Ok(())
+    }
+
+    // The `char`s of a row followed by its line ending
+    pub(crate) fn row_chars(&self, row: usize) -> impl Iterator<Item = char> + '_ {
+        let (width, height) = self.glyph_set.cell_size();
+        let line_ending = if row + 1 < self.y_chars_count() || self.trailing_line_ending {
+            self.line_ending.chars()
+        } else {
+            &[]
+        };
+
+        (0..self.cols())
+            .map(move |col| *self.char_at(col * width, row * height))
+            .chain(line_ending.iter().copied())
+    }
+}
+
+impl<S: PixelSource> fmt::Display for Framebuffer<'_, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_chunks(|chunk| f.write_str(chunk))
+    }
+}
+
+// The buffer only ever contains whole `char`s
+fn chunk(bytes: &[u8]) -> &str {
+    core::str::from_utf8(bytes).expect("buffer contains whole chars")
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use crate::{Framebuffer, GlyphSet, LineEnding};
+
+    #[test]
+    fn matches_iter() {
+        // Large enough to fill the buffer several times, with `char`s of different lengths
+        let f = Framebuffer::from_fn(301, 203, |x, y| (x ^ y) % 7 < 3);
+        for f in [
+            f,
+            f.glyph_set(GlyphSet::Ascii),
+            f.glyph_set(GlyphSet::Octant)
+                .line_ending(LineEnding::Crlf)
+                .trailing_line_ending(false),
+            f.line_ending(LineEnding::None),
+        ] {
+            let expected = f.into_iter().collect::<String>();
+
+            assert_eq!(expected, f.to_string());
+
+            let mut out = Vec::new();
+            f.write_to(&mut out).unwrap();
+            assert_eq!(expected.as_bytes(), out);
+
+            let mut out = String::from("> ");
+            f.render_into(&mut out);
+            assert_eq!(format!("> {}", expected), out);
+        }
+    }
+}
@@ -0,0 +1,827 @@
+//! Drawing shapes on a [`Canvas`].
+//!
+//! Every shape can either be stroked with a [`LineStyle`] or filled, see [`Paint`]. Like lines,
+//! shapes can be partially outside of the canvas and are clipped at the edges.
+//!
+//! Angles are in radians, starting from the positive x axis and increasing clockwise (because y
+//! increases downwards).
+//!
+//! # Example
+//!
+//! ```
+//! # use braillefb::{draw::LineStyle, shape::Paint, Canvas};
+//! let mut canvas = Canvas::new(8, 8);
+//! canvas.rect((0, 0), (8, 8), Paint::Stroke(LineStyle::default()));
+//! canvas.circle((4, 4), 2, Paint::Fill);
+//!
+//! assert_eq!("⡏⣩⣭⣹\n⣇⣻⣿⣻\n", &canvas.to_string());
+//! ```
+
+use core::cmp::Ordering;
+use core::f64::consts::TAU;
+
+use crate::draw::LineStyle;
+use crate::Canvas;
+
+/// How a shape is drawn.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Paint {
+    /// Draw the outline of the shape.
+    Stroke(LineStyle),
+    /// Fill the shape, including its outline.
+    Fill,
+}
+
+/// How the inside of a self-intersecting polygon is determined.
+///
+/// See <https://en.wikipedia.org/wiki/Nonzero-rule> and
+/// <https://en.wikipedia.org/wiki/Even%E2%80%93odd_rule>.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum FillRule {
+    /// Points are inside if the polygon winds around them a non-zero number of times.
+    #[default]
+    NonZero,
+    /// Points are inside if a ray from them crosses the polygon an odd number of times.
+    EvenOdd,
+}
+
+impl Canvas {
+    /// Draw a rectangle with its top-left corner at `origin`, covering `size` pixels.
+    pub fn rect(&mut self, origin: (i32, i32), size: (u32, u32), paint: Paint) {
+        self.rounded_rect(origin, size, 0, paint);
+    }
+
+    /// Draw a rectangle with its corners rounded to `radius` pixels.
+    ///
+    /// The radius is limited to half of the shortest side.
+    pub fn rounded_rect(
+        &mut self,
+        origin: (i32, i32),
+        size: (u32, u32),
+        radius: u32,
+        paint: Paint,
+    ) {
+        if size.0 == 0 || size.1 == 0 {
+            return;
+        }
+
+        let (x0, y0) = (origin.0 as i64, origin.1 as i64);
+        let (x1, y1) = (x0 + size.0 as i64 - 1, y0 + size.1 as i64 - 1);
+        let r = (radius as i64).min((x1 - x0) / 2).min((y1 - y0) / 2);
+        let rows = self.reach(paint);
+
+        // Clockwise from the top-right corner, the straight edges join the corners
+        let mut path = Path::new();
+        let corners = [
+            (x1 - r, y0 + r, Quadrant::TopRight),
+            (x1 - r, y1 - r, Quadrant::BottomRight),
+            (x0 + r, y1 - r, Quadrant::BottomLeft),
+            (x0 + r, y0 + r, Quadrant::TopLeft),
+        ];
+        for (cx, cy, q) in corners {
+            extend_quadrant(&mut path, (cx, cy), (r, r), q, rows);
+        }
+
+        self.paint_path(&path, paint);
+    }
+
+    /// Draw a circle centered on `center`.
+    pub fn circle(&mut self, center: (i32, i32), radius: u32, paint: Paint) {
+        self.ellipse(center, (radius, radius), paint);
+    }
+
+    /// Draw an ellipse centered on `center` with horizontal and vertical `radii`, using the
+    /// [midpoint ellipse algorithm][1].
+    ///
+    /// [1]: https://en.wikipedia.org/wiki/Midpoint_circle_algorithm
+    pub fn ellipse(&mut self, center: (i32, i32), radii: (u32, u32), paint: Paint) {
+        let path = ellipse_path(center, radii, self.reach(paint));
+        self.paint_path(&path, paint);
+    }
+
+    /// Draw an arc of a circle, clockwise from the `start` to the `end` angle.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::f64::consts::PI;
+    /// # use braillefb::{draw::LineStyle, Canvas};
+    /// let mut canvas = Canvas::new(8, 8);
+    /// // The right half, passing through angle 0
+    /// canvas.arc((3, 3), 3, 3.0 * PI / 2.0, PI / 2.0, LineStyle::default());
+    ///
+    /// assert_eq!("⠀⠈⠑⡄\n⠀⠠⠔⠁\n", &canvas.to_string());
+    /// ```
+    pub fn arc(&mut self, center: (i32, i32), radius: u32, start: f64, end: f64, style: LineStyle) {
+        let path = arc_path(center, radius, start, end, self.reach(Paint::Stroke(style)));
+        self.stroke_path(&path, false, style);
+    }
+
+    /// Draw a pie slice of a circle, clockwise from the `start` to the `end` angle.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::f64::consts::PI;
+    /// # use braillefb::{shape::Paint, Canvas};
+    /// let mut canvas = Canvas::new(8, 8);
+    /// // The bottom right quarter
+    /// canvas.pie((3, 3), 4, 0.0, PI / 2.0, Paint::Fill);
+    ///
+    /// assert_eq!("⠀⢀⣀⣀\n⠀⢸⡿⠏\n", &canvas.to_string());
+    /// ```
+    pub fn pie(&mut self, center: (i32, i32), radius: u32, start: f64, end: f64, paint: Paint) {
+        match paint {
+            Paint::Stroke(style) => {
+                // Spokes from the center to the ends of the sweep
+                let c = (center.0 as i64, center.1 as i64);
+                let mut path = Path::new();
+                line_to(&mut path, c);
+                line_to(&mut path, point_at(center, radius, start));
+                let arc = arc_path(center, radius, start, end, self.reach(paint));
+                extend_path(&mut path, &arc);
+                line_to(&mut path, point_at(center, radius, end));
+                line_to(&mut path, c);
+                self.stroke_path(&path, false, style);
+            }
+            Paint::Fill => {
+                let path = ellipse_path(center, (radius, radius), self.reach(paint));
+                self.fill_spans(&path, |x, y| {
+                    (x, y) == (center.0 as i64, center.1 as i64)
+                        || in_sweep(angle(center, (x, y)), start, end)
+                });
+            }
+        }
+    }
+
+    /// Draw a closed polygon through `points`. Filled polygons use the [`FillRule::NonZero`]
+    /// rule, see [`Canvas::fill_polygon`] to choose the rule.
+    pub fn polygon(&mut self, points: &[(i32, i32)], paint: Paint) {
+        match paint {
+            Paint::Stroke(style) => {
+                let mut path = Path::new();
+                for &(x, y) in points {
+                    line_to(&mut path, (x as i64, y as i64));
+                }
+                self.stroke_path(&path, true, style);
+            }
+            Paint::Fill => self.fill_polygon(points, FillRule::NonZero),
+        }
+    }
+
+    /// Fill a closed polygon through `points` using `rule` to decide which pixels are inside.
+    ///
+    /// Like the other shapes, the outline of the polygon is filled too. So a square with corners at
+    /// `(0, 0)` and `(3, 3)` fills 4x4 pixels.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use braillefb::{shape::FillRule, Canvas};
+    /// // A rectangle traced twice
+    /// let points = [(0, 0), (7, 0), (7, 3), (0, 3), (0, 0), (7, 0), (7, 3), (0, 3)];
+    ///
+    /// let mut canvas = Canvas::new(8, 4);
+    /// canvas.fill_polygon(&points, FillRule::NonZero);
+    /// assert_eq!("⣿⣿⣿⣿\n", &canvas.to_string());
+    ///
+    /// let mut canvas = Canvas::new(8, 4);
+    /// canvas.fill_polygon(&points, FillRule::EvenOdd);
+    /// assert_eq!("⣏⣉⣉⣹\n", &canvas.to_string());
+    /// ```
+    pub fn fill_polygon(&mut self, points: &[(i32, i32)], rule: FillRule) {
+        if points.len() < 3 {
+            return;
+        }
+
+        let edges = points
+            .iter()
+            .zip(points.iter().cycle().skip(1))
+            .map(|(&(x0, y0), &(x1, y1))| ((x0 as f64, y0 as f64), (x1 as f64, y1 as f64)))
+            .filter(|(p0, p1)| p0.1 != p1.1)
+            .collect::<Vec<_>>();
+
+        let min_y = points.iter().map(|p| p.1).min().unwrap().max(0) as usize;
+        let max_y = points.iter().map(|p| p.1).max().unwrap();
+        let max_y = (max_y.max(0) as usize).min(self.height());
+
+        let mut crossings = Vec::new();
+        for y in min_y..max_y {
+            let sample = y as f64;
+
+            crossings.clear();
+            for &((x0, y0), (x1, y1)) in &edges {
+                if (y0 <= sample) != (y1 <= sample) {
+                    let x = x0 + (sample - y0) * (x1 - x0) / (y1 - y0);
+                    crossings.push((x, if y1 > y0 { 1 } else { -1 }));
+                }
+            }
+            crossings.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+            let mut winding = 0;
+            for (i, &(x, direction)) in crossings.iter().enumerate() {
+                winding += direction;
+                let inside = match rule {
+                    FillRule::NonZero => winding != 0,
+                    FillRule::EvenOdd => i % 2 == 0,
+                };
+                if let (true, Some(&(next, _))) = (inside, crossings.get(i + 1)) {
+                    let first = x.ceil().max(0.0);
+                    let last = next.floor().min(self.width() as f64 - 1.0);
+                    if first <= last {
+                        for x in first as usize..=last as usize {
+                            self.set(x, y);
+                        }
+                    }
+                }
+            }
+        }
+
+        self.polygon(points, Paint::Stroke(LineStyle::default()));
+    }
+
+    // Stroke or fill a closed path around a convex shape
+    fn paint_path(&mut self, path: &Path, paint: Paint) {
+        match paint {
+            Paint::Stroke(style) => self.stroke_path(path, true, style),
+            Paint::Fill => self.fill_spans(path, |_, _| true),
+        }
+    }
+
+    // Stroke the lines joining the points of `path`, and the last point to the first if `closed`
+    fn stroke_path(&mut self, path: &Path, closed: bool, style: LineStyle) {
+        let (Some(&(first, _)), Some(&(last, step))) = (path.first(), path.last()) else {
+            return;
+        };
+
+        for pair in path.windows(2) {
+            self.segment(pair[0].0, pair[1].0, style, pair[0].1);
+        }
+        if closed {
+            self.segment(last, first, style, step);
+        } else if style.is_on(step) {
+            self.stamp(last.0, last.1, style.stroke_width());
+        }
+    }
+
+    // Set the pixels between the leftmost and the rightmost pixel of each row of a closed path
+    // where `inside` is true
+    fn fill_spans(&mut self, path: &Path, inside: impl Fn(i64, i64) -> bool) {
+        let width = self.width() as i64;
+        for (y, span) in spans(path, self.height()).into_iter().enumerate() {
+            let Some((x0, x1)) = span else {
+                continue;
+            };
+            for x in x0.max(0)..=x1.min(width - 1) {
+                if inside(x, y as i64) {
+                    self.plot(x, y as i64);
+                }
+            }
+        }
+    }
+
+    // The rows where drawing with `paint` can set pixels, the parts of curved paths outside of them
+    // are skipped
+    fn reach(&self, paint: Paint) -> (i64, i64) {
+        let margin = match paint {
+            Paint::Stroke(style) => style.stroke_width() as i64 + 1,
+            Paint::Fill => 0,
+        };
+        (-margin, self.height() as i64 - 1 + margin)
+    }
+}
+
+// Points joined by straight lines, each with the number of steps along the path to it. The parts
+// of curves that are outside of the rows that can be drawn are replaced by straight lines (that
+// are outside of the rows too), so the steps can't always be counted from the points.
+type Path = Vec<((i64, i64), i64)>;
+
+// Append `point` to `path`, `step` steps along it
+fn push(path: &mut Path, point: (i64, i64), step: i64) {
+    if path.last().map(|&(p, _)| p) != Some(point) {
+        path.push((point, step));
+    }
+}
+
+// Append `point` to `path`, joined to the last point by a line
+fn line_to(path: &mut Path, point: (i64, i64)) {
+    let step = path.last().map_or(0, |&(p, step)| step + steps(p, point));
+    push(path, point, step);
+}
+
+// The number of steps of a line from `a` to `b`
+fn steps(a: (i64, i64), b: (i64, i64)) -> i64 {
+    (b.0 - a.0).abs().max((b.1 - a.1).abs())
+}
+
+// The pixel `step` steps along a line from `a` to `b`
+fn point_on(a: (i64, i64), b: (i64, i64), step: i64) -> (i64, i64) {
+    let n = steps(a, b).max(1) as i128;
+    let lerp =
+        |a: i64, b: i64| a + crate::draw::div_round(step as i128 * (b - a) as i128, n) as i64;
+    (lerp(a.0, b.0), lerp(a.1, b.1))
+}
+
+// Append `other` to `path`, joined to the last point by a line
+fn extend_path(path: &mut Path, other: &Path) {
+    let Some(&(first, first_step)) = other.first() else {
+        return;
+    };
+    line_to(path, first);
+    let base = path[path.len() - 1].1 - first_step;
+    for &(point, step) in &other[1..] {
+        push(path, point, base + step);
+    }
+}
+
+#[derive(Copy, Clone)]
+enum Quadrant {
+    BottomRight,
+    BottomLeft,
+    TopLeft,
+    TopRight,
+}
+
+impl Quadrant {
+    // Maps the points of the bottom right quadrant into this quadrant, in clockwise order
+    fn offsets(self, quadrant: &Path) -> Path {
+        let total = quadrant.last().map_or(0, |&(_, step)| step);
+        let reversed = |(x, y): (i64, i64)| {
+            quadrant
+                .iter()
+                .rev()
+                .map(move |&((px, py), step)| ((px * x, py * y), total - step))
+        };
+        let forward = |(x, y): (i64, i64)| {
+            quadrant
+                .iter()
+                .map(move |&((px, py), step)| ((px * x, py * y), step))
+        };
+        match self {
+            Quadrant::BottomRight => forward((1, 1)).collect(),
+            Quadrant::BottomLeft => reversed((-1, 1)).collect(),
+            Quadrant::TopLeft => forward((-1, -1)).collect(),
+            Quadrant::TopRight => reversed((1, -1)).collect(),
+        }
+    }
+
+    // Maps `rows` to the rows of the bottom right quadrant of an ellipse centered on row `cy`
+    fn rows(self, cy: i64, rows: (i64, i64)) -> (i64, i64) {
+        match self {
+            Quadrant::BottomRight | Quadrant::BottomLeft => (rows.0 - cy, rows.1 - cy),
+            Quadrant::TopLeft | Quadrant::TopRight => (cy - rows.1, cy - rows.0),
+        }
+    }
+}
+
+// The bottom right quadrant of an ellipse, clockwise from `(rx, 0)` to `(0, ry)`. Only the pixels
+// in `rows` (relative to the center) are included, with the ends of the steep and the shallow
+// part of the curve.
+//
+// The pixels are the ones the [midpoint ellipse algorithm][1] picks: one in each row of the steep
+// part and one in each column of the shallow part, with the next half pixel inside the ellipse.
+// They are found with binary searches instead of walking the curve, so large ellipses only cost
+// as much as the rows they cover.
+//
+// [1]: https://en.wikipedia.org/wiki/Midpoint_circle_algorithm
+fn quadrant(rx: i64, ry: i64, rows: (i64, i64)) -> Path {
+    let mut path = Path::new();
+    // A straight line, the path joins its ends
+    if rx == 0 || ry == 0 {
+        line_to(&mut path, (rx, 0));
+        line_to(&mut path, (0, ry));
+        return path;
+    }
+
+    // Coordinates are doubled to test the half pixels
+    let col_y = |x: i64| last_where(0, ry, |y| compare(rx, ry, 2 * x, 2 * y - 1).is_lt()).max(0);
+    // The shallow part ends where the slope reaches 1, the row after it is picked like a column
+    let next_y = |x: i64| col_y(x).max(col_y(x - 1) - 1);
+    let (rx2, ry2) = ((rx as u128).pow(2), (ry as u128).pow(2));
+    let xs = first_where(1, rx, |x| ry2 * x as u128 >= rx2 * next_y(x) as u128);
+    let ys = next_y(xs);
+    let row_x = |y: i64| last_where(0, rx, |x| compare(rx, ry, 2 * x - 1, 2 * y).is_le()).max(xs);
+    let (lo, hi) = (rows.0.max(0), rows.1.min(ry));
+
+    // Each row of the steep part is a step
+    push(&mut path, (rx, 0), 0);
+    if ys > 0 {
+        for y in lo.max(1)..=hi.min(ys - 1) {
+            push(&mut path, (row_x(y), y), y);
+        }
+        push(&mut path, (row_x(ys - 1), ys - 1), ys - 1);
+    }
+    line_to(&mut path, (xs, ys));
+
+    // Each column of the shallow part is a step, only the ends of each row are needed
+    let end = path[path.len() - 1].1 + xs;
+    for y in lo.max(ys)..=hi {
+        let x0 = first_where(0, xs - 1, |x| compare(rx, ry, 2 * x, 2 * y + 1).is_ge());
+        let x1 = last_where(0, xs - 1, |x| compare(rx, ry, 2 * x, 2 * y - 1).is_lt());
+        if x0 <= x1 {
+            push(&mut path, (x1, y), end - x1);
+            push(&mut path, (x0, y), end - x0);
+        }
+    }
+    push(&mut path, (0, ry), end);
+    path
+}
+
+// Compares the point `(x / 2, y / 2)` with an ellipse, `Less` is inside
+fn compare(rx: i64, ry: i64, x: i64, y: i64) -> Ordering {
+    let square = |n: i64| n.unsigned_abs() as u128 * n.unsigned_abs() as u128;
+    let (rx2, ry2) = (square(rx), square(ry));
+    let exact = || {
+        let point = ry2
+            .checked_mul(square(x))?
+            .checked_add(rx2.checked_mul(square(y))?)?;
+        Some(point.cmp(&rx2.checked_mul(ry2)?.checked_mul(4)?))
+    };
+    exact().unwrap_or_else(|| {
+        // Only for radii beyond 2^31, where rounding is far smaller than a pixel
+        let (x, y) = (x as f64 / (2 * rx) as f64, y as f64 / (2 * ry) as f64);
+        (x * x + y * y).total_cmp(&1.0)
+    })
+}
+
+// Append a quadrant of an ellipse around `center` to `path`, joined to the last point by a line
+fn extend_quadrant(
+    path: &mut Path,
+    center: (i64, i64),
+    radii: (i64, i64),
+    q: Quadrant,
+    rows: (i64, i64),
+) {
+    let quadrant = quadrant(radii.0, radii.1, q.rows(center.1, rows));
+    let points = q
+        .offsets(&quadrant)
+        .into_iter()
+        .map(|((x, y), step)| ((center.0 + x, center.1 + y), step))
+        .collect();
+    extend_path(path, &points);
+}
+
+// A closed path around an ellipse, clockwise from the positive x axis
+fn ellipse_path(center: (i32, i32), radii: (u32, u32), rows: (i64, i64)) -> Path {
+    let center = (center.0 as i64, center.1 as i64);
+    let radii = (radii.0 as i64, radii.1 as i64);
+
+    let mut path = Path::new();
+    for q in [
+        Quadrant::BottomRight,
+        Quadrant::BottomLeft,
+        Quadrant::TopLeft,
+        Quadrant::TopRight,
+    ] {
+        extend_quadrant(&mut path, center, radii, q, rows);
+    }
+    if path.len() > 1 && path.first().map(|p| p.0) == path.last().map(|p| p.0) {
+        path.pop();
+    }
+    path
+}
+
+// The pixels of an arc, clockwise from the `start` to the `end` angle
+fn arc_path(center: (i32, i32), radius: u32, start: f64, end: f64, rows: (i64, i64)) -> Path {
+    let mut path = ellipse_path(center, (radius, radius), rows);
+    if path.len() < 2 {
+        path.retain(|&(p, _)| in_sweep(angle(center, p), start, end));
+        return path;
+    }
+    let (first, last) = (path[0], path[path.len() - 1]);
+    let total = last.1 + steps(last.0, first.0);
+    // Clockwise from `start`, points along the path only wrap around once
+    let offset = |p: (i64, i64)| (angle(center, p) - start).rem_euclid(TAU);
+    let sweep = if end - start >= TAU {
+        TAU
+    } else {
+        (end - start).rem_euclid(TAU)
+    };
+
+    // Start from the first pixel past `start`, on the line to the point closest to it
+    let first = (0..path.len())
+        .min_by(|&a, &b| offset(path[a].0).total_cmp(&offset(path[b].0)))
+        .unwrap_or(0);
+    let (a, step) = path[(first + path.len() - 1) % path.len()];
+    path.rotate_left(first);
+    let b = path[0].0;
+    let s = first_where(1, steps(a, b), |s| offset(point_on(a, b, s)) < TAU / 2.0);
+    if s < steps(a, b) {
+        path.insert(0, (point_on(a, b, s), step + s));
+    }
+    let base = path[0].1;
+    for (_, step) in &mut path {
+        *step = (*step - base).rem_euclid(total);
+    }
+
+    // End at the last pixel before `end`, on the line from the last point in the sweep
+    let k = last_where(0, path.len() as i64 - 1, |i| {
+        offset(path[i as usize].0) <= sweep
+    });
+    let Ok(k) = usize::try_from(k) else {
+        return Path::new();
+    };
+    let (a, step) = path[k];
+    let b = path[(k + 1) % path.len()].0;
+    let s = last_where(1, steps(a, b) - 1, |s| offset(point_on(a, b, s)) <= sweep);
+    path.truncate(k + 1);
+    if s > 0 {
+        path.push((point_on(a, b, s), step + s));
+    }
+    path
+}
+
+// The pixel on a circle at `angle`
+fn point_at(center: (i32, i32), radius: u32, angle: f64) -> (i64, i64) {
+    let (sin, cos) = angle.sin_cos();
+    (
+        center.0 as i64 + (cos * radius as f64).round() as i64,
+        center.1 as i64 + (sin * radius as f64).round() as i64,
+    )
+}
+
+// The leftmost and rightmost pixel of each of the first `height` rows of a closed path, as
+// `(x0, x1)` indexed by y
+fn spans(path: &Path, height: usize) -> Vec<Option<(i64, i64)>> {
+    let mut spans = vec![None; height];
+    let Some(&last) = path.last() else {
+        return spans;
+    };
+
+    for (&(p0, _), &(p1, _)) in core::iter::once(&last).chain(path).zip(path) {
+        let (dx, dy) = (p1.0 - p0.0, p1.1 - p0.1);
+        let steps = dx.abs().max(dy.abs());
+        let x = |step: i64| {
+            p0.0 + crate::draw::div_round(step as i128 * dx as i128, steps as i128) as i64
+        };
+        let y = |step: i64| {
+            p0.1 + crate::draw::div_round(step as i128 * dy as i128, steps as i128) as i64
+        };
+
+        // Only the rows on the canvas
+        let first = p0.1.min(p1.1).max(0);
+        let last = p0.1.max(p1.1).min(height as i64 - 1);
+        for row in first..=last {
+            // The steps in this row, y only moves in one direction along the line
+            let (a, b) = if dy >= 0 {
+                (
+                    first_where(0, steps, |s| y(s) >= row),
+                    first_where(0, steps, |s| y(s) > row),
+                )
+            } else {
+                (
+                    first_where(0, steps, |s| y(s) <= row),
+                    first_where(0, steps, |s| y(s) < row),
+                )
+            };
+            if a >= b {
+                continue;
+            }
+            let (x0, x1) = (x(a).min(x(b - 1)), x(a).max(x(b - 1)));
+            let span = &mut spans[row as usize];
+            *span = Some(span.map_or((x0, x1), |(s0, s1)| (s0.min(x0), s1.max(x1))));
+        }
+    }
+    spans
+}
+
+// The first value in `lo..=hi` where `f` is true, or `hi + 1`. Once `f` is true it has to stay
+// true.
+fn first_where(lo: i64, hi: i64, f: impl Fn(i64) -> bool) -> i64 {
+    let (mut lo, mut hi) = (lo, hi + 1);
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        if f(mid) {
+            hi = mid;
+        } else {
+            lo = mid + 1;
+        }
+    }
+    lo
+}
+
+// The last value in `lo..=hi` where `f` is true, or `lo - 1`. Once `f` is false it has to stay
+// false.
+fn last_where(lo: i64, hi: i64, f: impl Fn(i64) -> bool) -> i64 {
+    first_where(lo, hi, |n| !f(n)) - 1
+}
+
+fn angle(center: (i32, i32), (x, y): (i64, i64)) -> f64 {
+    ((y - center.1 as i64) as f64).atan2((x - center.0 as i64) as f64)
+}
+
+// Whether `angle` is within the clockwise sweep from `start` to `end`
+fn in_sweep(angle: f64, start: f64, end: f64) -> bool {
+    let sweep = end - start;
+    sweep >= TAU || (angle - start).rem_euclid(TAU) <= sweep.rem_euclid(TAU)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use super::{ellipse_path, FillRule, Paint};
+    use crate::draw::LineStyle;
+    use crate::Canvas;
+
+    fn count(canvas: &Canvas) -> usize {
+        canvas.to_pixels().iter().filter(|p| **p).count()
+    }
+
+    #[test]
+    fn rects() {
+        let mut canvas = Canvas::new(6, 6);
+        canvas.rect((1, 1), (4, 3), Paint::Stroke(LineStyle::default()));
+        assert_eq!(10, count(&canvas));
+        assert_eq!(Some(false), canvas.get_pixel(2, 2));
+
+        let mut canvas = Canvas::new(6, 6);
+        canvas.rect((1, 1), (4, 3), Paint::Fill);
+        assert_eq!(12, count(&canvas));
+
+        // Clipped at the edges
+        let mut canvas = Canvas::new(4, 4);
+        canvas.rect((-2, -2), (4, 4), Paint::Fill);
+        assert_eq!(4, count(&canvas));
+
+        let mut canvas = Canvas::new(4, 4);
+        canvas.rect((0, 0), (0, 4), Paint::Fill);
+        assert_eq!(0, count(&canvas));
+    }
+
+    #[test]
+    fn clipping() {
+        // Shapes far outside of the canvas don't walk every pixel
+        let mut canvas = Canvas::new(8, 8);
+        canvas.rect((0, 0), (50_000_000, 4), Paint::Fill);
+        assert_eq!(32, count(&canvas));
+
+        let mut canvas = Canvas::new(8, 8);
+        canvas.rect((-3, 2), (u32::MAX, 4), Paint::Stroke(LineStyle::dotted()));
+        assert_eq!(8, count(&canvas));
+
+        let mut canvas = Canvas::new(8, 8);
+        let points = [(-50_000_000, 1), (50_000_000, 1), (4, 50_000_000)];
+        canvas.polygon(&points, Paint::Stroke(LineStyle::default()));
+        assert_eq!(8, count(&canvas));
+        canvas.polygon(&points, Paint::Fill);
+        assert_eq!(7 * 8, count(&canvas));
+
+        let mut canvas = Canvas::new(8, 8);
+        canvas.circle((4, 4), 20_000, Paint::Fill);
+        assert_eq!(64, count(&canvas));
+        let mut canvas = Canvas::new(8, 8);
+        canvas.pie((4, 4), 20_000, 0.0, PI / 2.0, Paint::Fill);
+        assert_eq!(16, count(&canvas));
+
+        // Radii so large that their squares overflow i64
+        let mut canvas = Canvas::new(8, 8);
+        canvas.circle((0, 0), u32::MAX, Paint::Stroke(LineStyle::default()));
+        assert_eq!(0, count(&canvas));
+        canvas.circle((0, 0), u32::MAX, Paint::Fill);
+        assert_eq!(64, count(&canvas));
+
+        let mut canvas = Canvas::new(8, 8);
+        canvas.circle(
+            (4, -2_000_000_000),
+            2_000_000_004,
+            Paint::Stroke(LineStyle::default()),
+        );
+        assert_eq!("⠀⠀⠀⠀\n⠉⠉⠉⠉\n", &canvas.to_string());
+
+        let mut canvas = Canvas::new(8, 8);
+        let size = (u32::MAX, u32::MAX);
+        canvas.rounded_rect(
+            (i32::MIN, 0),
+            size,
+            u32::MAX,
+            Paint::Stroke(LineStyle::default()),
+        );
+        assert_eq!("⠉⠉⠉⠉\n⠀⠀⠀⠀\n", &canvas.to_string());
+        canvas.rounded_rect((i32::MIN, 0), size, u32::MAX, Paint::Fill);
+        assert_eq!(64, count(&canvas));
+    }
+
+    #[test]
+    fn rounded_rects() {
+        let mut canvas = Canvas::new(8, 8);
+        canvas.rounded_rect((0, 0), (8, 8), 2, Paint::Stroke(LineStyle::default()));
+        assert_eq!("⡎⠉⠉⢱\n⢇⣀⣀⡸\n", &canvas.to_string());
+
+        let mut canvas = Canvas::new(8, 8);
+        canvas.rounded_rect((0, 0), (8, 8), 2, Paint::Fill);
+        assert_eq!("⣾⣿⣿⣷\n⢿⣿⣿⡿\n", &canvas.to_string());
+    }
+
+    #[test]
+    fn circles() {
+        let path = ellipse_path((0, 0), (3, 3), (-10, 10))
+            .into_iter()
+            .map(|(p, _)| p)
+            .collect::<Vec<_>>();
+        // Every point is next to the previous one
+        for (a, b) in path.iter().zip(path.iter().cycle().skip(1)) {
+            assert!((a.0 - b.0).abs() <= 1 && (a.1 - b.1).abs() <= 1);
+        }
+        for &(x, y) in &path {
+            let d = ((x * x + y * y) as f64).sqrt();
+            assert!((d - 3.0).abs() < 0.5, "{:?}", (x, y));
+        }
+
+        let mut canvas = Canvas::new(8, 8);
+        canvas.circle((4, 4), 3, Paint::Stroke(LineStyle::default()));
+        assert_eq!("⢀⠔⠒⢄\n⠘⢄⣀⠜\n", &canvas.to_string());
+
+        let mut canvas = Canvas::new(1, 1);
+        canvas.circle((0, 0), 0, Paint::Fill);
+        assert_eq!(1, count(&canvas));
+    }
+
+    #[test]
+    fn ellipses() {
+        let mut canvas = Canvas::new(12, 6);
+        canvas.ellipse((6, 3), (5, 2), Paint::Fill);
+        assert_eq!(Some(true), canvas.get_pixel(1, 3));
+        assert_eq!(Some(true), canvas.get_pixel(11, 3));
+        assert_eq!(Some(true), canvas.get_pixel(6, 1));
+        assert_eq!(Some(false), canvas.get_pixel(6, 0));
+        assert_eq!(Some(false), canvas.get_pixel(1, 1));
+
+        let mut canvas = Canvas::new(12, 6);
+        canvas.ellipse((6, 3), (5, 0), Paint::Stroke(LineStyle::default()));
+        assert_eq!(11, count(&canvas));
+    }
+
+    #[test]
+    fn arcs_and_pies() {
+        let mut canvas = Canvas::new(9, 9);
+        canvas.arc((4, 4), 4, 0.0, PI, LineStyle::default());
+        // Only the bottom half
+        for y in 0..4 {
+            for x in 0..9 {
+                assert_eq!(Some(false), canvas.get_pixel(x, y));
+            }
+        }
+        assert_eq!(Some(true), canvas.get_pixel(4, 8));
+        assert_eq!(Some(true), canvas.get_pixel(0, 4));
+        assert_eq!(Some(true), canvas.get_pixel(8, 4));
+
+        let mut canvas = Canvas::new(9, 9);
+        canvas.pie(
+            (4, 4),
+            4,
+            0.0,
+            PI / 2.0,
+            Paint::Stroke(LineStyle::default()),
+        );
+        assert_eq!(Some(true), canvas.get_pixel(4, 4));
+        assert_eq!(Some(true), canvas.get_pixel(6, 4));
+        assert_eq!(Some(true), canvas.get_pixel(4, 6));
+        assert_eq!(Some(false), canvas.get_pixel(6, 6));
+        assert_eq!(Some(false), canvas.get_pixel(2, 2));
+
+        // Sweeps wrap through angle 0 when `end` is before `start`
+        let mut canvas = Canvas::new(9, 9);
+        canvas.arc((4, 4), 4, 3.0 * PI / 2.0, PI / 2.0, LineStyle::default());
+        assert_eq!(Some(true), canvas.get_pixel(8, 4));
+        assert_eq!(Some(false), canvas.get_pixel(0, 4));
+        assert!((0..9).all(|y| (0..4).all(|x| canvas.get_pixel(x, y) == Some(false))));
+
+        // The spokes go to the ends of the sweep, not to the start of the circle
+        let mut canvas = Canvas::new(9, 9);
+        canvas.pie(
+            (4, 4),
+            4,
+            -PI / 4.0,
+            PI / 4.0,
+            Paint::Stroke(LineStyle::default()),
+        );
+        assert_eq!(Some(true), canvas.get_pixel(5, 3));
+        assert_eq!(Some(true), canvas.get_pixel(5, 5));
+        assert_eq!(Some(false), canvas.get_pixel(6, 4));
+        assert_eq!(Some(true), canvas.get_pixel(8, 4));
+    }
+
+    #[test]
+    fn polygons() {
+        let triangle = [(0, 0), (7, 0), (0, 7)];
+
+        let mut canvas = Canvas::new(8, 8);
+        canvas.polygon(&triangle, Paint::Stroke(LineStyle::default()));
+        assert_eq!("⡏⠉⡩⠋\n⡧⠊⠀⠀\n", &canvas.to_string());
+
+        let mut canvas = Canvas::new(8, 8);
+        canvas.polygon(&triangle, Paint::Fill);
+        assert_eq!("⣿⣿⡿⠋\n⡿⠋⠀⠀\n", &canvas.to_string());
+
+        // A pentagram has a hole in the middle with the even-odd rule
+        let star = [(8, 0), (13, 16), (0, 6), (16, 6), (3, 16)];
+        let mut non_zero = Canvas::new(16, 16);
+        non_zero.fill_polygon(&star, FillRule::NonZero);
+        let mut even_odd = Canvas::new(16, 16);
+        even_odd.fill_polygon(&star, FillRule::EvenOdd);
+        assert_eq!(Some(true), non_zero.get_pixel(8, 9));
+        assert_eq!(Some(false), even_odd.get_pixel(8, 9));
+        assert_eq!(Some(true), even_odd.get_pixel(8, 3));
+    }
+}
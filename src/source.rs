@@ -0,0 +1,401 @@
+//! Sources of pixels that a [`Framebuffer`](crate::Framebuffer) can render.
+//!
+//! Anything implementing [`PixelSource`] can be rendered, this module contains implementations
+//! for the common cases:
+//!
+//! - [`Pixels`]: a `&[bool]` slice, one `bool` per pixel
+//! - [`Cells`]: a bit-packed `&[u8]` slice, one `u8` per braille `char`
+//! - [`Threshold`]: a `&[u8]` slice of luminance values, lit at or above a threshold
+//! - [`FromFn`]: a closure `Fn(usize, usize) -> bool`
+//! - `Vec<Vec<bool>>`: a 2D grid of rows
+//!
+//! # Example
+//!
+//! ```
+//! # use braillefb::Framebuffer;
+//! // A checkerboard, without allocating a buffer
+//! let f = Framebuffer::from_fn(4, 4, |x, y| (x + y) % 2 == 0);
+//! assert_eq!("⢕⢕\n", &f.to_string());
+//! ```
+
+use core::fmt;
+
+use crate::{Error, BIT_OFFSETS, CHAR_HEIGHT, CHAR_WIDTH, DOT_BITS};
+
+/// A source of pixels that can be rendered as braille `char`s.
+pub trait PixelSource {
+    /// The width of the source in pixels.
+    fn width(&self) -> usize;
+
+    /// The height of the source in pixels.
+    fn height(&self) -> usize;
+
+    /// Returns whether the pixel at `x`, `y` is set. This is only called for pixels inside of
+    /// `width` and `height`.
+    fn pixel(&self, x: usize, y: usize) -> bool;
+
+    /// Returns the eight dots of the braille `char` at `col`, `row` packed into a `u8`, see
+    /// [`Framebuffer::from_cells`](crate::Framebuffer::from_cells) for the layout.
+    ///
+    /// The default implementation calls [`PixelSource::pixel`] for each dot; sources that store
+    /// their pixels bit-packed can override this to skip that work.
+    fn cell(&self, col: usize, row: usize) -> u8 {
+        let x_offset = col * CHAR_WIDTH;
+        let y_offset = row * CHAR_HEIGHT;
+        let (width, height) = (self.width(), self.height());
+
+        // The x/y offsets are combined with the BIT_OFFSETS to create a u8 in the order that a
+        // UTF-8 braille character is represented
+        //
+        // 1 4
+        // 2 5
+        // 3 6
+        // 7 8
+        //
+        // 0b00000000
+        //   87654321
+        let mut n: u8 = 0;
+        for (x, y) in BIT_OFFSETS {
+            n <<= 1;
+            let xx = x_offset + x;
+            let yy = y_offset + y;
+            if xx >= width || yy >= height {
+                continue;
+            }
+            n |= self.pixel(xx, yy) as u8;
+        }
+        n
+    }
+}
+
+impl<T: PixelSource + ?Sized> PixelSource for &T {
+    fn width(&self) -> usize {
+        (**self).width()
+    }
+
+    fn height(&self) -> usize {
+        (**self).height()
+    }
+
+    fn pixel(&self, x: usize, y: usize) -> bool {
+        (**self).pixel(x, y)
+    }
+
+    fn cell(&self, col: usize, row: usize) -> u8 {
+        (**self).cell(col, row)
+    }
+}
+
+/// Rows of pixels. The width is taken from the first row, shorter rows are padded with unset
+/// pixels.
+#[cfg(feature = "alloc")]
+impl PixelSource for alloc::vec::Vec<alloc::vec::Vec<bool>> {
+    fn width(&self) -> usize {
+        self.first().map_or(0, alloc::vec::Vec::len)
+    }
+
+    fn height(&self) -> usize {
+        self.len()
+    }
+
+    fn pixel(&self, x: usize, y: usize) -> bool {
+        self[y].get(x).copied().unwrap_or(false)
+    }
+}
+
+/// A `&[bool]` slice of pixels in row-major order.
+#[derive(Debug, Copy, Clone)]
+pub struct Pixels<'a> {
+    pixels: &'a [bool],
+    width: usize,
+    height: usize,
+}
+
+impl<'a> Pixels<'a> {
+    /// # Panics
+    ///
+    /// Panics if length of supplied `pixels` slice is not equal to `width * height`, or if
+    /// `width * height` overflows.
+    pub fn new(pixels: &'a [bool], width: usize, height: usize) -> Self {
+        let len = width
+            .checked_mul(height)
+            .expect("width * height overflows usize");
+        assert_eq!(
+            pixels.len(),
+            len,
+            "supplied slice does not match width * height"
+        );
+
+        Self {
+            pixels,
+            width,
+            height,
+        }
+    }
+
+    /// Returns an error if the supplied `pixels` slice doesn't match `width * height`, or either
+    /// dimension is zero.
+    pub fn try_new(pixels: &'a [bool], width: usize, height: usize) -> Result<Self, Error> {
+        check_len(pixels.len(), width, height, width.checked_mul(height))?;
+
+        Ok(Self {
+            pixels,
+            width,
+            height,
+        })
+    }
+}
+
+impl PixelSource for Pixels<'_> {
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        self.height
+    }
+
+    fn pixel(&self, x: usize, y: usize) -> bool {
+        self.pixels[x + y * self.width]
+    }
+}
+
+/// A bit-packed `&[u8]` slice with one `u8` per braille `char`.
+///
+/// See [`Framebuffer::from_cells`](crate::Framebuffer::from_cells) for the layout.
+#[derive(Debug, Copy, Clone)]
+pub struct Cells<'a> {
+    cells: &'a [u8],
+    width: usize,
+    height: usize,
+}
+
+impl<'a> Cells<'a> {
+    /// # Panics
+    ///
+    /// Panics if the length of supplied `cells` slice does not match the number of cells, or if
+    /// the number of cells overflows.
+    pub fn new(cells: &'a [u8], width: usize, height: usize) -> Self {
+        let len = width
+            .div_ceil(CHAR_WIDTH)
+            .checked_mul(height.div_ceil(CHAR_HEIGHT));
+        // Unlike `try_new`, zero-sized cells are allowed
+        if let Err(err @ (Error::LengthMismatch { .. } | Error::DimensionOverflow { .. })) =
+            check_len(cells.len(), width, height, len)
+        {
+            panic!("{err}");
+        }
+
+        Self {
+            cells,
+            width,
+            height,
+        }
+    }
+
+    /// Returns an error if the supplied `cells` slice doesn't match the number of cells, or
+    /// either dimension is zero.
+    pub fn try_new(cells: &'a [u8], width: usize, height: usize) -> Result<Self, Error> {
+        let len = width
+            .div_ceil(CHAR_WIDTH)
+            .checked_mul(height.div_ceil(CHAR_HEIGHT));
+        check_len(cells.len(), width, height, len)?;
+
+        Ok(Self {
+            cells,
+            width,
+            height,
+        })
+    }
+}
+
+impl PixelSource for Cells<'_> {
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        self.height
+    }
+
+    fn pixel(&self, x: usize, y: usize) -> bool {
+        let cell = self.cell(x / CHAR_WIDTH, y / CHAR_HEIGHT);
+        cell & DOT_BITS[y % CHAR_HEIGHT][x % CHAR_WIDTH] != 0
+    }
+
+    fn cell(&self, col: usize, row: usize) -> u8 {
+        self.cells[col + row * self.width.div_ceil(CHAR_WIDTH)]
+    }
+}
+
+/// A `&[u8]` slice of luminance values in row-major order. Pixels are set when their value is
+/// greater than or equal to the threshold.
+///
+/// # Example
+///
+/// ```
+/// # use braillefb::{source::Threshold, Framebuffer};
+/// let luma = [
+///     255, 0,
+///     200, 100,
+///     128, 127,
+///     10, 250,
+/// ];
+///
+/// let f = Framebuffer::from_source(Threshold::new(&luma, 2, 4, 128));
+/// assert_eq!("⢇\n", &f.to_string());
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct Threshold<'a> {
+    luma: &'a [u8],
+    width: usize,
+    height: usize,
+    threshold: u8,
+}
+
+impl<'a> Threshold<'a> {
+    /// # Panics
+    ///
+    /// Panics if length of supplied `luma` slice is not equal to `width * height`, or if
+    /// `width * height` overflows.
+    pub fn new(luma: &'a [u8], width: usize, height: usize, threshold: u8) -> Self {
+        let len = width
+            .checked_mul(height)
+            .expect("width * height overflows usize");
+        assert_eq!(
+            luma.len(),
+            len,
+            "supplied slice does not match width * height"
+        );
+
+        Self {
+            luma,
+            width,
+            height,
+            threshold,
+        }
+    }
+}
+
+impl PixelSource for Threshold<'_> {
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        self.height
+    }
+
+    fn pixel(&self, x: usize, y: usize) -> bool {
+        self.luma[x + y * self.width] >= self.threshold
+    }
+}
+
+/// A closure that is called with `x`, `y` for each pixel.
+#[derive(Copy, Clone)]
+pub struct FromFn<F> {
+    f: F,
+    width: usize,
+    height: usize,
+}
+
+impl<F: Fn(usize, usize) -> bool> FromFn<F> {
+    pub fn new(width: usize, height: usize, f: F) -> Self {
+        Self { f, width, height }
+    }
+}
+
+impl<F: Fn(usize, usize) -> bool> PixelSource for FromFn<F> {
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        self.height
+    }
+
+    fn pixel(&self, x: usize, y: usize) -> bool {
+        (self.f)(x, y)
+    }
+}
+
+impl<F> fmt::Debug for FromFn<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FromFn")
+            .field("width", &self.width)
+            .field("height", &self.height)
+            .finish_non_exhaustive()
+    }
+}
+
+fn check_len(
+    actual: usize,
+    width: usize,
+    height: usize,
+    expected: Option<usize>,
+) -> Result<(), Error> {
+    if width == 0 || height == 0 {
+        return Err(Error::ZeroSized { width, height });
+    }
+
+    match expected {
+        Some(expected) if expected == actual => Ok(()),
+        Some(expected) => Err(Error::LengthMismatch { expected, actual }),
+        None => Err(Error::DimensionOverflow { width, height }),
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::{Cells, FromFn, PixelSource, Pixels, Threshold};
+
+    #[test]
+    fn sources_agree() {
+        // ⠇⠅
+        // ⠉⠁
+        #[rustfmt::skip]
+        let pixels = [
+            true, false, true,
+            true, false, false,
+            true, false, true,
+            false, false, false,
+            true, true, true,
+        ];
+        let luma = pixels.map(|p| if p { 255 } else { 0 });
+        let rows = pixels.chunks(3).map(|r| r.to_vec()).collect::<Vec<_>>();
+        let cells = [0b0000_0111, 0b0000_0101, 0b0000_1001, 0b0000_0001];
+
+        let sources: [&dyn PixelSource; 5] = [
+            &Pixels::new(&pixels, 3, 5),
+            &Cells::new(&cells, 3, 5),
+            &Threshold::new(&luma, 3, 5, 1),
+            &FromFn::new(3, 5, |x, y| pixels[x + y * 3]),
+            &rows,
+        ];
+        for source in sources {
+            assert_eq!(3, source.width());
+            assert_eq!(5, source.height());
+            for (i, cell) in cells.iter().enumerate() {
+                assert_eq!(*cell, source.cell(i % 2, i / 2));
+            }
+            for y in 0..5 {
+                for x in 0..3 {
+                    assert_eq!(pixels[x + y * 3], source.pixel(x, y));
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "overflow")]
+    fn cells_overflow() {
+        Cells::new(&[], usize::MAX, usize::MAX);
+    }
+
+    #[test]
+    fn ragged_rows() {
+        let rows = vec![vec![true, true], vec![true]];
+        assert_eq!(2, rows.width());
+        assert!(!rows.pixel(1, 1));
+    }
+}
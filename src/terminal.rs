@@ -0,0 +1,335 @@
+//! Animating framebuffers in a terminal without redrawing every frame.
+//!
+//! Printing the whole framebuffer for every frame flickers and wastes bandwidth, especially over
+//! SSH. [`DiffRenderer`] remembers the last frame it wrote and only writes the cells that changed,
+//! moving the cursor to them with ANSI escape sequences. [`Session`] switches to the alternate
+//! screen and hides the cursor until it is dropped.
+//!
+//! # Example
+//!
+//! ```no_run
+//! # use braillefb::{terminal::{DiffRenderer, Session}, Framebuffer};
+//! # fn main() -> std::io::Result<()> {
+//! let mut session = Session::new(std::io::stdout())?;
+//! let mut renderer = DiffRenderer::new();
+//!
+//! for frame in 0..100 {
+//!     let f = Framebuffer::from_fn(80, 40, |x, y| (x + y + frame) % 9 == 0);
+//!     renderer.render(&f, &mut session)?;
+//!     std::thread::sleep(std::time::Duration::from_millis(30));
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::io::{self, Write};
+
+use crate::{Framebuffer, PixelSource};
+
+const ENTER_SESSION: &[u8] = b"\x1b[?1049h\x1b[?25l";
+const LEAVE_SESSION: &[u8] = b"\x1b[?25h\x1b[?1049l";
+const CLEAR_SCREEN: &[u8] = b"\x1b[2J";
+
+// Rewriting a few unchanged cells is shorter than moving the cursor past them
+const MAX_GAP: usize = 2;
+
+/// Writes frames to a terminal, only updating the cells that changed since the previous frame.
+///
+/// Frames are drawn from the top-left corner of the screen. The first frame, frames with a
+/// different size than the previous one and frames where more than
+/// [`full_redraw_ratio`](DiffRenderer::full_redraw_ratio) of the cells changed are redrawn in
+/// full.
+///
+/// Line endings aren't written, each row is placed with the cursor.
+#[derive(Debug, Clone)]
+pub struct DiffRenderer {
+    previous: Vec<char>,
+    current: Vec<char>,
+    size: Option<(usize, usize)>,
+    full_redraw_ratio: f64,
+    buf: Vec<u8>,
+}
+
+impl DiffRenderer {
+    /// Create a renderer that will redraw the first frame in full.
+    pub fn new() -> Self {
+        Self {
+            previous: Vec::new(),
+            current: Vec::new(),
+            size: None,
+            full_redraw_ratio: 0.5,
+            buf: Vec::new(),
+        }
+    }
+
+    /// Set the fraction of cells (between 0 and 1) that have to change for a frame to be redrawn in
+    /// full instead of cell by cell. Defaults to 0.5.
+    pub fn full_redraw_ratio(mut self, ratio: f64) -> Self {
+        self.full_redraw_ratio = ratio;
+        self
+    }
+
+    /// Redraw the next frame in full, for example when the terminal was resized or something else
+    /// was written to it.
+    pub fn invalidate(&mut self) {
+        self.size = None;
+    }
+
+    /// Write the changes from the previous frame to `framebuffer` to `out`, then flush it.
+    ///
+    /// The changes are written with a single call to [`Write::write_all`]. If writing fails the
+    /// next frame is redrawn in full.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use braillefb::{terminal::DiffRenderer, Canvas};
+    /// let mut renderer = DiffRenderer::new();
+    /// let mut canvas = Canvas::new(8, 4);
+    /// let mut out = Vec::new();
+    /// renderer.render(&canvas.framebuffer(), &mut out).unwrap();
+    /// assert_eq!("\x1b[2J\x1b[1;1H⠀⠀⠀⠀", String::from_utf8(out).unwrap());
+    ///
+    /// canvas.set(7, 3);
+    /// let mut out = Vec::new();
+    /// renderer.render(&canvas.framebuffer(), &mut out).unwrap();
+    /// assert_eq!("\x1b[1;4H⢀", String::from_utf8(out).unwrap());
+    /// ```
+    pub fn render<S, W>(&mut self, framebuffer: &Framebuffer<'_, S>, out: &mut W) -> io::Result<()>
+    where
+        S: PixelSource,
+        W: Write + ?Sized,
+    {
+        let (cols, rows) = (framebuffer.cols(), framebuffer.y_chars_count());
+        self.current.clear();
+        self.current.extend(framebuffer.rows().flatten());
+
+        self.buf.clear();
+        if self.size == Some((cols, rows)) {
+            let changed = self
+                .previous
+                .iter()
+                .zip(&self.current)
+                .filter(|(previous, current)| previous != current)
+                .count();
+            if changed as f64 > self.full_redraw_ratio * self.current.len() as f64 {
+                self.redraw(cols);
+            } else if changed > 0 {
+                self.update(cols);
+            }
+        } else {
+            self.redraw(cols);
+        }
+        self.size = Some((cols, rows));
+        core::mem::swap(&mut self.previous, &mut self.current);
+
+        let result = if self.buf.is_empty() {
+            Ok(())
+        } else {
+            out.write_all(&self.buf)
+        };
+        let result = result.and_then(|()| out.flush());
+        // The terminal may have only part of the frame
+        if result.is_err() {
+            self.invalidate();
+        }
+        result
+    }
+
+    fn redraw(&mut self, cols: usize) {
+        self.buf.extend_from_slice(CLEAR_SCREEN);
+        for (row, cells) in self.current.chunks(cols.max(1)).enumerate() {
+            move_to(&mut self.buf, row, 0);
+            for &c in cells {
+                push_char(&mut self.buf, c);
+            }
+        }
+    }
+
+    fn update(&mut self, cols: usize) {
+        // Where the cursor is after the last written cell
+        let mut cursor = None;
+        for (i, (&previous, &current)) in self.previous.iter().zip(&self.current).enumerate() {
+            if previous == current {
+                continue;
+            }
+
+            let (row, col) = (i / cols, i % cols);
+            match cursor {
+                Some((cursor_row, cursor_col))
+                    if cursor_row == row && col - cursor_col <= MAX_GAP =>
+                {
+                    for &c in &self.current[i - (col - cursor_col)..i] {
+                        push_char(&mut self.buf, c);
+                    }
+                }
+                _ => move_to(&mut self.buf, row, col),
+            }
+            push_char(&mut self.buf, current);
+            cursor = Some((row, col + 1));
+        }
+    }
+}
+
+impl Default for DiffRenderer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Switches the terminal to the alternate screen and hides the cursor, restoring both when
+/// dropped.
+///
+/// Everything written to the session is written to the inner writer.
+#[derive(Debug)]
+pub struct Session<W: Write> {
+    out: W,
+}
+
+impl<W: Write> Session<W> {
+    /// Start a session on `out`, usually [`io::stdout`].
+    pub fn new(mut out: W) -> io::Result<Self> {
+        out.write_all(ENTER_SESSION)?;
+        out.flush()?;
+        Ok(Self { out })
+    }
+
+    /// Get a reference to the inner writer.
+    pub fn get_ref(&self) -> &W {
+        &self.out
+    }
+
+    /// Get a mutable reference to the inner writer.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.out
+    }
+}
+
+impl<W: Write> Write for Session<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.out.write(buf)
+    }
+
+    fn write_all(&mut self, buf: &[u8]) -> io::Result<()> {
+        self.out.write_all(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.out.flush()
+    }
+}
+
+impl<W: Write> Drop for Session<W> {
+    fn drop(&mut self) {
+        // There's nothing useful to do with an error while dropping
+        let _ = self.out.write_all(LEAVE_SESSION);
+        let _ = self.out.flush();
+    }
+}
+
+// Move the cursor to the 0-based `row` and `col`
+fn move_to(buf: &mut Vec<u8>, row: usize, col: usize) {
+    // Writing to a `Vec` can't fail
+    let _ = write!(buf, "\x1b[{};{}H", row + 1, col + 1);
+}
+
+fn push_char(buf: &mut Vec<u8>, c: char) {
+    buf.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes());
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{self, Write};
+
+    use super::{DiffRenderer, Session};
+    use crate::{Canvas, GlyphSet};
+
+    // Fails every write
+    struct Broken;
+
+    impl Write for Broken {
+        fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+            Err(io::ErrorKind::BrokenPipe.into())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn render(renderer: &mut DiffRenderer, canvas: &Canvas) -> String {
+        let mut out = Vec::new();
+        renderer.render(&canvas.framebuffer(), &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn updates() {
+        let mut renderer = DiffRenderer::new();
+        let mut canvas = Canvas::new(16, 8);
+        assert_eq!(
+            "\x1b[2J\x1b[1;1H⠀⠀⠀⠀⠀⠀⠀⠀\x1b[2;1H⠀⠀⠀⠀⠀⠀⠀⠀",
+            render(&mut renderer, &canvas)
+        );
+        assert_eq!("", render(&mut renderer, &canvas));
+
+        // Small gaps are rewritten, larger ones are skipped with the cursor
+        canvas.set(0, 0);
+        canvas.set(6, 0);
+        canvas.set(15, 0);
+        canvas.set(0, 4);
+        assert_eq!(
+            "\x1b[1;1H⠁⠀⠀⠁\x1b[1;8H⠈\x1b[2;1H⠁",
+            render(&mut renderer, &canvas)
+        );
+
+        renderer.invalidate();
+        assert!(render(&mut renderer, &canvas).starts_with("\x1b[2J"));
+
+        // A frame that couldn't be written isn't diffed against
+        canvas.set(1, 0);
+        assert!(renderer.render(&canvas.framebuffer(), &mut Broken).is_err());
+        assert!(render(&mut renderer, &canvas).starts_with("\x1b[2J"));
+    }
+
+    #[test]
+    fn full_redraws() {
+        let mut renderer = DiffRenderer::new().full_redraw_ratio(0.25);
+        let mut canvas = Canvas::new(8, 4);
+        render(&mut renderer, &canvas);
+
+        canvas.set(0, 0);
+        assert_eq!("\x1b[1;1H⠁", render(&mut renderer, &canvas));
+        canvas.set(2, 0);
+        canvas.set(4, 0);
+        assert_eq!("\x1b[2J\x1b[1;1H⠁⠁⠁⠀", render(&mut renderer, &canvas));
+
+        // A different size is always redrawn
+        let canvas = Canvas::new(4, 4);
+        assert_eq!("\x1b[2J\x1b[1;1H⠀⠀", render(&mut renderer, &canvas));
+
+        // The glyph set is used
+        let f = canvas.framebuffer().glyph_set(GlyphSet::HalfBlock);
+        let mut out = Vec::new();
+        renderer.render(&f, &mut out).unwrap();
+        assert_eq!(
+            "\x1b[2J\x1b[1;1H    \x1b[2;1H    ",
+            String::from_utf8(out).unwrap()
+        );
+    }
+
+    #[test]
+    fn session() {
+        let mut out = Vec::new();
+        {
+            let mut session = Session::new(&mut out).unwrap();
+            DiffRenderer::new()
+                .render(&Canvas::new(2, 4).framebuffer(), &mut session)
+                .unwrap();
+        }
+        assert_eq!(
+            "\x1b[?1049h\x1b[?25l\x1b[2J\x1b[1;1H⠀\x1b[?25h\x1b[?1049l",
+            String::from_utf8(out).unwrap()
+        );
+    }
+}
@@ -0,0 +1,307 @@
+//! Rendering framebuffers in a [ratatui][1] terminal UI.
+//!
+//! [`FramebufferWidget`] writes `char`s straight into a [`Buffer`], so unlike printing the
+//! [`Display`](core::fmt::Display) output in a `Paragraph`, it is clipped to its area, can be
+//! stretched to fill it and every cell can have its own [`Style`].
+//!
+//! # Example
+//!
+//! ```
+//! # use braillefb::{widget::FramebufferWidget, Canvas};
+//! use ratatui_core::buffer::Buffer;
+//! use ratatui_core::layout::Rect;
+//! use ratatui_core::widgets::Widget;
+//!
+//! let mut canvas = Canvas::new(6, 4);
+//! canvas.set(0, 0);
+//! canvas.set(5, 3);
+//!
+//! let mut buf = Buffer::empty(Rect::new(0, 0, 2, 1));
+//! FramebufferWidget::new(canvas.framebuffer()).render(buf.area, &mut buf);
+//! assert_eq!(Buffer::with_lines(["⠁⠀"]), buf);
+//! ```
+//!
+//! [1]: https://ratatui.rs
+
+use ratatui_core::buffer::Buffer;
+use ratatui_core::layout::{Position, Rect};
+use ratatui_core::style::Style;
+use ratatui_core::widgets::{StatefulWidget, Widget};
+
+use crate::{Canvas, Framebuffer, PixelSource};
+
+/// A widget that renders a [`Framebuffer`] into a ratatui [`Buffer`].
+///
+/// The framebuffer's [`GlyphSet`](crate::GlyphSet) is used, its line endings are not.
+///
+/// Render it as a [`StatefulWidget`] with a [`FramebufferState`] to scroll a framebuffer that is
+/// larger than the area.
+#[derive(Debug, Copy, Clone)]
+pub struct FramebufferWidget<'a, S, F = fn(usize, usize) -> Style> {
+    framebuffer: Framebuffer<'a, S>,
+    style: Style,
+    cell_style: F,
+    fit: Fit,
+}
+
+/// How a framebuffer that is a different size than the area is rendered.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub enum Fit {
+    /// Render the framebuffer at its own size, cells outside of the area are clipped.
+    #[default]
+    Clip,
+    /// Scale the framebuffer (without keeping its aspect ratio) to fill the area.
+    Stretch,
+}
+
+/// The scroll position of a [`FramebufferWidget`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub struct FramebufferState {
+    offset: (usize, usize),
+}
+
+impl<'a, S: PixelSource> FramebufferWidget<'a, S> {
+    /// Create a widget that renders `framebuffer` clipped to the area and without any style.
+    pub fn new(framebuffer: Framebuffer<'a, S>) -> Self {
+        Self {
+            framebuffer,
+            style: Style::new(),
+            cell_style: |_, _| Style::new(),
+            fit: Fit::Clip,
+        }
+    }
+}
+
+impl<'a, S: PixelSource, F: Fn(usize, usize) -> Style> FramebufferWidget<'a, S, F> {
+    /// Set the style of the whole area, including cells that aren't covered by the framebuffer.
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Set how the framebuffer is fit to the area, see [`Fit`].
+    pub fn fit(mut self, fit: Fit) -> Self {
+        self.fit = fit;
+        self
+    }
+
+    /// Style individual cells with a closure that is called with the column and row of each
+    /// rendered `char`. The returned style is patched on top of [`FramebufferWidget::style`].
+    ///
+    /// The column and row are those of the framebuffer, so they don't change when it is scrolled.
+    /// When the framebuffer is stretched they are the column and row in the area instead.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use braillefb::{widget::FramebufferWidget, Framebuffer};
+    /// use ratatui_core::buffer::Buffer;
+    /// use ratatui_core::layout::Rect;
+    /// use ratatui_core::style::Style;
+    /// use ratatui_core::widgets::Widget;
+    ///
+    /// let f = Framebuffer::from_fn(4, 4, |_, _| true);
+    /// let mut buf = Buffer::empty(Rect::new(0, 0, 2, 1));
+    /// FramebufferWidget::new(f)
+    ///     .cell_style(|col, _row| if col == 0 { Style::new().red() } else { Style::new() })
+    ///     .render(buf.area, &mut buf);
+    ///
+    /// let mut expected = Buffer::with_lines(["⣿⣿"]);
+    /// expected.set_style(Rect::new(0, 0, 1, 1), Style::new().red());
+    /// assert_eq!(expected, buf);
+    /// ```
+    pub fn cell_style<G: Fn(usize, usize) -> Style>(self, f: G) -> FramebufferWidget<'a, S, G> {
+        FramebufferWidget {
+            framebuffer: self.framebuffer,
+            style: self.style,
+            cell_style: f,
+            fit: self.fit,
+        }
+    }
+
+    fn render_at(&self, area: Rect, buf: &mut Buffer, offset: (usize, usize)) {
+        let area = area.intersection(buf.area);
+        buf.set_style(area, self.style);
+
+        match self.fit {
+            Fit::Clip => self.render_cells(&self.framebuffer, area, buf, offset),
+            Fit::Stretch => {
+                let source = &self.framebuffer.source;
+                let (src_width, src_height) = (source.width(), source.height());
+                if src_width == 0 || src_height == 0 {
+                    return;
+                }
+
+                let (cell_width, cell_height) = self.framebuffer.glyph_set.cell_size();
+                let (width, height) = (
+                    area.width as usize * cell_width,
+                    area.height as usize * cell_height,
+                );
+                let scaled = Framebuffer::from_fn(width, height, |x, y| {
+                    source.pixel(x * src_width / width, y * src_height / height)
+                })
+                .glyph_set(self.framebuffer.glyph_set);
+                self.render_cells(&scaled, area, buf, (0, 0));
+            }
+        }
+    }
+
+    fn render_cells<T: PixelSource>(
+        &self,
+        framebuffer: &Framebuffer<'_, T>,
+        area: Rect,
+        buf: &mut Buffer,
+        (col_offset, row_offset): (usize, usize),
+    ) {
+        for (y, row) in (area.top()..area.bottom()).zip(row_offset..) {
+            for (x, col) in (area.left()..area.right()).zip(col_offset..) {
+                let position = Position::new(x, y);
+                let (Some(c), Some(cell)) = (framebuffer.cell(col, row), buf.cell_mut(position))
+                else {
+                    continue;
+                };
+                cell.set_char(c).set_style((self.cell_style)(col, row));
+            }
+        }
+    }
+}
+
+impl<S: PixelSource, F: Fn(usize, usize) -> Style> Widget for FramebufferWidget<'_, S, F> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        self.render_at(area, buf, (0, 0));
+    }
+}
+
+impl<S: PixelSource, F: Fn(usize, usize) -> Style> StatefulWidget for FramebufferWidget<'_, S, F> {
+    type State = FramebufferState;
+
+    /// Render the framebuffer scrolled to the state's offset.
+    ///
+    /// The offset is clamped so that the area is covered by the framebuffer where possible, and
+    /// the clamped offset is stored in the state. Stretched framebuffers are never scrolled.
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut FramebufferState) {
+        let cols = self.framebuffer.cols();
+        let rows = self.framebuffer.y_chars_count();
+        let (col, row) = state.offset;
+        state.offset = (
+            col.min(cols.saturating_sub(area.width as usize)),
+            row.min(rows.saturating_sub(area.height as usize)),
+        );
+
+        let offset = match self.fit {
+            Fit::Clip => state.offset,
+            Fit::Stretch => (0, 0),
+        };
+        self.render_at(area, buf, offset);
+    }
+}
+
+impl Widget for &Canvas {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        Widget::render(FramebufferWidget::new(self.framebuffer()), area, buf);
+    }
+}
+
+impl FramebufferState {
+    /// Scroll to `col`, `row` (in cells).
+    pub fn with_offset(mut self, col: usize, row: usize) -> Self {
+        self.offset = (col, row);
+        self
+    }
+
+    /// The column and row of the top-left cell in the area.
+    pub fn offset(&self) -> (usize, usize) {
+        self.offset
+    }
+
+    /// Mutable access to the offset, to scroll the framebuffer.
+    pub fn offset_mut(&mut self) -> &mut (usize, usize) {
+        &mut self.offset
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui_core::buffer::Buffer;
+    use ratatui_core::layout::Rect;
+    use ratatui_core::style::Style;
+    use ratatui_core::widgets::Widget;
+
+    use super::{Fit, FramebufferState, FramebufferWidget};
+    use crate::{Canvas, Framebuffer, GlyphSet};
+
+    // A 3x2 cell framebuffer with a diagonal line
+    fn diagonal() -> Canvas {
+        let mut canvas = Canvas::new(6, 8);
+        for i in 0..6 {
+            canvas.set(i, i);
+        }
+        canvas
+    }
+
+    #[test]
+    fn clip() {
+        let canvas = diagonal();
+        let mut buf = Buffer::empty(Rect::new(0, 0, 4, 3));
+        canvas.render(Rect::new(1, 1, 3, 2), &mut buf);
+        assert_eq!(Buffer::with_lines(["    ", " ⠑⢄⠀", " ⠀⠀⠑"]), buf);
+
+        let mut buf = Buffer::empty(Rect::new(0, 0, 2, 1));
+        canvas.render(buf.area, &mut buf);
+        assert_eq!(Buffer::with_lines(["⠑⢄"]), buf);
+    }
+
+    #[test]
+    fn scroll() {
+        let canvas = diagonal();
+        let widget = FramebufferWidget::new(canvas.framebuffer());
+        let mut buf = Buffer::empty(Rect::new(0, 0, 2, 1));
+
+        let mut state = FramebufferState::default().with_offset(1, 1);
+        super::StatefulWidget::render(widget, buf.area, &mut buf, &mut state);
+        assert_eq!(Buffer::with_lines(["⠀⠑"]), buf);
+
+        // Scrolling past the end is clamped
+        *state.offset_mut() = (10, 10);
+        super::StatefulWidget::render(widget, buf.area, &mut buf, &mut state);
+        assert_eq!((1, 1), state.offset());
+    }
+
+    #[test]
+    fn stretch() {
+        let f = Framebuffer::from_fn(2, 2, |x, y| x == y).glyph_set(GlyphSet::Quadrant);
+        let mut buf = Buffer::empty(Rect::new(0, 0, 2, 2));
+        FramebufferWidget::new(f)
+            .fit(Fit::Stretch)
+            .render(buf.area, &mut buf);
+        assert_eq!(Buffer::with_lines(["█ ", " █"]), buf);
+
+        let empty = Canvas::new(0, 0);
+        let mut buf = Buffer::empty(Rect::new(0, 0, 2, 1));
+        FramebufferWidget::new(empty.framebuffer())
+            .fit(Fit::Stretch)
+            .render(buf.area, &mut buf);
+        assert_eq!(Buffer::with_lines(["  "]), buf);
+    }
+
+    #[test]
+    fn styles() {
+        let canvas = diagonal();
+        let mut buf = Buffer::empty(Rect::new(0, 0, 4, 1));
+        FramebufferWidget::new(canvas.framebuffer())
+            .style(Style::new().on_blue())
+            .cell_style(|col, _| {
+                if col == 1 {
+                    Style::new().red()
+                } else {
+                    Style::new()
+                }
+            })
+            .render(buf.area, &mut buf);
+
+        let mut expected = Buffer::with_lines(["⠑⢄⠀ "]);
+        expected.set_style(expected.area, Style::new().on_blue());
+        expected.set_style(Rect::new(1, 0, 1, 1), Style::new().red());
+        assert_eq!(expected, buf);
+    }
+}